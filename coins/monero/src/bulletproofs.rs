@@ -0,0 +1,236 @@
+//! A native Rust range-proof verifier, reimplementing the Bulletproofs paper's verification
+//! equation directly rather than calling out to the Monero C++ verifier over FFI.
+//!
+//! This is a partial delivery of its originating request, which asked for native verification of
+//! "Bulletproofs (and Bulletproofs+) range proofs and for CLSAG signatures": only classic
+//! [`Bulletproof`] verification ([`verify`]) is implemented here. [`verify_plus`] is a permanent,
+//! honestly-documented stub returning `false` rather than a real Bulletproofs+ verifier, and CLSAG
+//! signature verification was never started — no `clsag.rs` exists in this revision of the crate.
+//! The BP+ gap is why `SignableTransaction::multisig` refuses to negotiate a Bulletproofs+
+//! protocol at all (see [`verify_plus`]'s doc comment), rather than risk misusing the stub; the
+//! CLSAG gap has no such guard, since nothing in this revision calls a CLSAG verifier in the first
+//! place.
+
+use sha3::{Digest, Keccak256};
+
+use curve25519_dalek::{
+  constants::ED25519_BASEPOINT_POINT,
+  scalar::Scalar,
+  edwards::{EdwardsPoint, CompressedEdwardsY},
+};
+
+use multiexp::multiexp_vartime;
+
+use monero::{
+  consensus::Encodable,
+  util::ringct::{Bulletproof, BulletproofPlus},
+};
+
+// Monero aggregates up to this many 64-bit range proofs into a single Bulletproof
+const BIT_LENGTH: usize = 64;
+const MAX_OUTPUTS: usize = 16;
+
+/// Either format of Monero range proof `generate` (not defined in this file) may produce,
+/// depending on the negotiated [`crate::Protocol`].
+pub enum Bulletproofs {
+  Original(Bulletproof),
+  Plus(BulletproofPlus),
+}
+
+impl Encodable for Bulletproofs {
+  fn consensus_encode<W: std::io::Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+    match self {
+      Bulletproofs::Original(bp) => bp.consensus_encode(writer),
+      Bulletproofs::Plus(bp) => bp.consensus_encode(writer),
+    }
+  }
+}
+
+fn hash_to_scalar(data: &[u8]) -> Scalar {
+  Scalar::from_bytes_mod_order(Keccak256::digest(data).into())
+}
+
+// A simple try-and-increment hash-to-point, used to derive generators no one knows the discrete
+// log of relative to the curve's basepoint or to one another
+fn hash_to_point(data: &[u8]) -> EdwardsPoint {
+  let mut bytes: [u8; 32] = Keccak256::digest(data).into();
+  loop {
+    if let Some(point) = CompressedEdwardsY(bytes).decompress() {
+      return point.mul_by_cofactor();
+    }
+    bytes = Keccak256::digest(bytes).into();
+  }
+}
+
+// The per-index `Gi`/`Hi` generator vectors the range proof's inner-product argument runs over
+fn bit_generators(count: usize) -> (Vec<EdwardsPoint>, Vec<EdwardsPoint>) {
+  let mut g = Vec::with_capacity(count);
+  let mut h = Vec::with_capacity(count);
+  for i in 0 .. count {
+    g.push(hash_to_point(&[b"bulletproof-G", &u32::try_from(i).unwrap().to_le_bytes()[..]].concat()));
+    h.push(hash_to_point(&[b"bulletproof-H", &u32::try_from(i).unwrap().to_le_bytes()[..]].concat()));
+  }
+  (g, h)
+}
+
+fn log2(mut value: usize) -> usize {
+  let mut log = 0;
+  while value > 1 {
+    value >>= 1;
+    log += 1;
+  }
+  log
+}
+
+// sum_{i=0}^{terms-1} base^i
+fn geometric_sum(base: Scalar, terms: usize) -> Scalar {
+  let mut sum = Scalar::ZERO;
+  let mut term = Scalar::ONE;
+  for _ in 0 .. terms {
+    sum += term;
+    term *= base;
+  }
+  sum
+}
+
+/// Verify an aggregate Bulletproof range proof for `commitments`, natively in Rust via
+/// `multiexp_vartime`, without any FFI into the Monero C++ range-proof verifier.
+///
+/// This reimplements the Bulletproofs paper's aggregate range proof verification equation in two
+/// parts: the `t_hat`/polynomial-commitment check, then the inner-product argument opening `P`,
+/// reconstructing the per-generator fold scalars in O(n) via the same doubling recurrence used
+/// for [`crate`'s FCMP++ inner-product argument](../crypto/generalized-bulletproofs).
+///
+/// Bulletproofs+ verification isn't implemented by this function yet (see
+/// [`verify_plus`](mod@self)) — it's a different, non-polynomial-commitment protocol, not a minor
+/// variant of this one, and so is left for a follow-up rather than risk a subtly wrong bolt-on.
+pub fn verify(bp: &Bulletproof, commitments: &[EdwardsPoint]) -> bool {
+  let m = commitments.len();
+  if (m == 0) || (m > MAX_OUTPUTS) {
+    return false;
+  }
+  let padded_m = m.next_power_of_two();
+  let n = padded_m * BIT_LENGTH;
+  let rounds = log2(n);
+  if (bp.L.len() != rounds) || (bp.R.len() != rounds) {
+    return false;
+  }
+
+  let comm_g = ED25519_BASEPOINT_POINT;
+  let comm_h = hash_to_point(b"bulletproof-value-generator");
+  let (g_bold, h_bold) = bit_generators(n);
+
+  let mut transcript = vec![];
+  transcript.extend(bp.A.compress().to_bytes());
+  transcript.extend(bp.S.compress().to_bytes());
+  let y = hash_to_scalar(&transcript);
+  if y == Scalar::ZERO {
+    return false;
+  }
+  transcript.extend(y.to_bytes());
+  let z = hash_to_scalar(&transcript);
+  if z == Scalar::ZERO {
+    return false;
+  }
+
+  transcript.extend(z.to_bytes());
+  transcript.extend(bp.T1.compress().to_bytes());
+  transcript.extend(bp.T2.compress().to_bytes());
+  let x = hash_to_scalar(&transcript);
+  if x == Scalar::ZERO {
+    return false;
+  }
+
+  // delta(y, z) = (z - z^2) * sum(y^i, i in 0 .. n) - sum(z^(3+j) * sum(2^i, i in 0 .. BIT_LENGTH), j in 0 .. padded_m)
+  let delta = ((z - (z * z)) * geometric_sum(y, n)) -
+    (0 .. padded_m).fold(Scalar::ZERO, |acc, j| {
+      acc + (z.pow(&[(3 + j) as u64]) * geometric_sum(Scalar::from(2u8), BIT_LENGTH))
+    });
+
+  // t_hat * comm_h + taux * comm_g =? delta * comm_h + sum(z^(2+j) * V_j) + x * T1 + x^2 * T2
+  let mut rhs_terms = vec![(delta, comm_h), (x, bp.T1), (x * x, bp.T2)];
+  for (j, commitment) in commitments.iter().enumerate() {
+    rhs_terms.push((z.pow(&[(2 + j) as u64]), *commitment));
+  }
+  let rhs = multiexp_vartime(&rhs_terms);
+  let lhs = multiexp_vartime(&[(bp.t, comm_h), (bp.taux, comm_g)]);
+  if lhs != rhs {
+    return false;
+  }
+
+  // Fold the L/R challenges to recompute the inner-product argument's opening P
+  let mut challenges = Vec::with_capacity(rounds);
+  for (L, R) in bp.L.iter().zip(bp.R.iter()) {
+    transcript.extend(L.compress().to_bytes());
+    transcript.extend(R.compress().to_bytes());
+    let e = hash_to_scalar(&transcript);
+    if e == Scalar::ZERO {
+      return false;
+    }
+    transcript.extend(e.to_bytes());
+    challenges.push(e);
+  }
+  let challenges_inv: Vec<Scalar> = challenges.iter().map(Scalar::invert).collect();
+
+  // Per-generator scalars an honest prover's folds would've applied, via the O(n) doubling
+  // recurrence: index 0 carries every round's inverse challenge, and flipping index i's lowest
+  // set bit from 0 to 1 swaps that round's inverse challenge for the challenge squared
+  let mut g_scalars = vec![Scalar::ONE; n];
+  g_scalars[0] = challenges_inv.iter().fold(Scalar::ONE, |acc, e_inv| acc * e_inv);
+  for i in 1 .. n {
+    let bit = i.trailing_zeros() as usize;
+    let round = rounds - 1 - bit;
+    g_scalars[i] = g_scalars[i - (1 << bit)] * (challenges[round] * challenges[round]);
+  }
+  // h_bold folds with the inverse weighting, relative to g_bold, at every round
+  let h_scalars: Vec<Scalar> = (0 .. n).map(|i| g_scalars[n - 1 - i]).collect();
+
+  let y_inv = y.invert();
+  let mut y_inv_pow = Scalar::ONE;
+  let mut p_terms = Vec::with_capacity((2 * n) + (2 * rounds) + 3);
+  for i in 0 .. n {
+    let two_i_mod_bit_length = Scalar::from(1u64 << (i % BIT_LENGTH));
+    let z_pow_j = z.pow(&[(i / BIT_LENGTH) as u64]);
+
+    p_terms.push((-z, g_bold[i]));
+    // h'_i = h_i * y^-i: the h-side scalar is expressed directly in terms of y^-i so the two
+    // cancel, leaving z plus the bit-position term
+    p_terms.push(((z * y_inv_pow) + ((z * z) * z_pow_j * two_i_mod_bit_length * y_inv_pow), h_bold[i]));
+
+    y_inv_pow *= y_inv;
+  }
+  for ((e, e_inv), (L, R)) in challenges.iter().zip(challenges_inv.iter()).zip(bp.L.iter().zip(bp.R.iter())) {
+    p_terms.push((*e * *e, *L));
+    p_terms.push((*e_inv * *e_inv, *R));
+  }
+  p_terms.push((Scalar::ONE, bp.A));
+  p_terms.push((x, bp.S));
+  let P = multiexp_vartime(&p_terms);
+
+  // P should open, under the reconstructed fold scalars, to <a, g_bold> + <b, h_bold> + (a*b)*comm_h,
+  // net of the blind `mu` already removed by the prover from `mu`-adjusted generators
+  let mut opening_terms = Vec::with_capacity(n + n + 2);
+  for i in 0 .. n {
+    opening_terms.push((g_scalars[i] * bp.a, g_bold[i]));
+    opening_terms.push((h_scalars[i] * bp.b, h_bold[i]));
+  }
+  opening_terms.push((bp.a * bp.b, comm_h));
+  opening_terms.push((bp.mu, comm_g));
+  let opening = multiexp_vartime(&opening_terms);
+
+  P == opening
+}
+
+/// Verify a Bulletproofs+ range proof. Not yet implemented natively: Bulletproofs+ replaces the
+/// polynomial commitment phase `verify` checks above with a weighted inner-product argument, so
+/// it needs its own verification equation rather than a small variant of `verify`'s. Until that's
+/// written, this conservatively rejects every proof rather than accept one unchecked.
+///
+/// Because this always returns `false`, it must never be wired into a path that treats rejection
+/// as a peer's fault (e.g. a multisig share failing to verify) — `SignableTransaction::multisig`
+/// refuses to even construct a [`crate::transaction::multisig::TransactionMachine`] for a BP+
+/// protocol for exactly this reason, rather than let every non-leader signer look like it submitted
+/// an invalid share.
+pub fn verify_plus(_bp: &BulletproofPlus, _commitments: &[EdwardsPoint]) -> bool {
+  false
+}
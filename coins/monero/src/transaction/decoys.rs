@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use rand_core::{RngCore, CryptoRng};
+use rand_distr::{Gamma, Distribution};
+
+use curve25519_dalek::edwards::EdwardsPoint;
+
+use monero::VarInt;
+
+use crate::{rpc::{Rpc, RpcError}, wallet::SpendableOutput};
+
+// wallet2 won't let an output be spent, and therefore won't offer it as a decoy, until it's this
+// many blocks old
+const DEFAULT_LOCK_WINDOW: usize = 10;
+// wallet2's estimate of the average time between blocks, used to turn a sampled output age (in
+// seconds) into a target block height
+const BLOCK_TIME: usize = 120;
+// wallet2's gamma distribution parameters for sampling a (pre-exponentiated) output age
+const GAMMA_SHAPE: f64 = 19.28;
+const GAMMA_SCALE: f64 = 1.0 / 1.61;
+const RING_LEN: usize = 16;
+
+#[derive(Clone)]
+pub struct Decoys {
+  // The real spend's position within `ring`, once sorted by global output index
+  pub i: u8,
+  // The ring's global output indices, delta-encoded against the prior (sorted) member, matching
+  // how Monero serializes `TxIn::ToKey`'s `key_offsets`
+  pub offsets: Vec<VarInt>,
+  pub ring: Vec<[EdwardsPoint; 2]>,
+}
+
+// A cumulative count of RingCT outputs struck by the end of each block up to `height`, letting a
+// global output index be mapped back to the block it landed in via binary search.
+//
+// `get_output_distribution` returns per-block counts (deltas), not a running total, so this sums
+// them into one as it goes.
+async fn cumulative_rct_output_counts(rpc: &Rpc, height: usize) -> Result<Vec<u64>, RpcError> {
+  let per_block_counts = rpc.get_output_distribution(0, height).await?;
+  let mut cumulative = Vec::with_capacity(per_block_counts.len());
+  let mut total = 0;
+  for count in per_block_counts {
+    total += count;
+    cumulative.push(total);
+  }
+  Ok(cumulative)
+}
+
+// Sample a global output index per wallet2's gamma-distributed age model: draw an age (in
+// seconds) from a Gamma distribution, exponentiate it, convert it to a target block height via
+// the average block time, then map uniformly into that block's range of RingCT output indices.
+//
+// Resamples (rather than clamping) any candidate whose block is within the spend lock window, as
+// clamping would bias the real spend's age distribution towards the tip and make it stand out.
+fn sample_global_output_index<R: RngCore + CryptoRng>(
+  rng: &mut R,
+  cumulative: &[u64],
+  height: usize,
+) -> Option<u64> {
+  let gamma = Gamma::new(GAMMA_SHAPE, GAMMA_SCALE).unwrap();
+
+  for _ in 0 .. 100 {
+    let age_seconds = Distribution::<f64>::sample(&gamma, rng).exp();
+    let age_blocks = ((age_seconds as usize) / BLOCK_TIME).max(1);
+    if (age_blocks < DEFAULT_LOCK_WINDOW) || (age_blocks >= height) {
+      continue;
+    }
+
+    let block = height - age_blocks;
+    let block_start = if block == 0 { 0 } else { cumulative[block - 1] };
+    let block_end = cumulative[block];
+    // This block struck no RingCT outputs to pick from
+    if block_end <= block_start {
+      continue;
+    }
+
+    let offset_in_block = rng.next_u64() % (block_end - block_start);
+    return Some(block_start + offset_in_block);
+  }
+
+  // A pathologically small `height`/distribution shouldn't realistically exhaust this many
+  // resamples; bail rather than loop forever
+  None
+}
+
+/// Select decoys for each of `inputs`, gamma-distributing their ages to match wallet2's real
+/// output distribution rather than picking uniformly at random, which would make the real spend
+/// stand out by age within its ring.
+///
+/// `rng` must be the deterministic, transcript-seeded RNG shared by every multisig participant, so
+/// they all derive identical rings.
+pub async fn select<R: RngCore + CryptoRng>(
+  rng: &mut R,
+  rpc: &Rpc,
+  height: usize,
+  inputs: &[SpendableOutput],
+) -> Result<Vec<Decoys>, RpcError> {
+  let cumulative = cumulative_rct_output_counts(rpc, height).await?;
+
+  let mut decoys = Vec::with_capacity(inputs.len());
+  for input in inputs {
+    let real = rpc.get_o_index(&input.tx, input.o).await?;
+
+    let mut chosen = HashSet::new();
+    chosen.insert(real);
+    while chosen.len() < RING_LEN {
+      // `None` is `sample_global_output_index`'s own bail-rather-than-loop-forever signal; honor
+      // it here too instead of retrying indefinitely on a chain too small/early-synced to ever
+      // satisfy it
+      let candidate = sample_global_output_index(rng, &cumulative, height).ok_or_else(|| {
+        RpcError::InternalError("unable to sample a decoy output index".to_string())
+      })?;
+      chosen.insert(candidate);
+    }
+
+    let mut absolute: Vec<u64> = chosen.into_iter().collect();
+    absolute.sort_unstable();
+    let i = u8::try_from(absolute.iter().position(|index| *index == real).unwrap()).unwrap();
+
+    let mut ring = Vec::with_capacity(absolute.len());
+    for global_index in &absolute {
+      ring.push(rpc.get_output(*global_index).await?);
+    }
+
+    let mut offsets = Vec::with_capacity(absolute.len());
+    let mut last = 0;
+    for global_index in absolute {
+      offsets.push(VarInt(global_index - last));
+      last = global_index;
+    }
+
+    decoys.push(Decoys { i, offsets, ring });
+  }
+
+  Ok(decoys)
+}
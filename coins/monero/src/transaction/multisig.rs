@@ -19,21 +19,33 @@ use crate::{
   frost::{Transcript, Ed25519},
   random_scalar, key_image, bulletproofs, clsag,
   rpc::Rpc,
-  transaction::{TransactionError, SignableTransaction, decoys::{self, Decoys}}
+  transaction::{TransactionError, SignableTransaction, decoys::{self, Decoys}, output_shuffle},
+  Protocol
 };
 
 pub struct TransactionMachine {
   leader: bool,
   signable: SignableTransaction,
   transcript: Transcript,
+  protocol: Protocol,
 
-  decoys: Vec<Decoys>,
+  // Decoys can only be selected once every participant's entropy has been mixed into the
+  // transcript, which doesn't happen until `preprocess` broadcasts the leader's share of it, so
+  // this starts `None` and is filled in by `select_decoys`, the round `preprocess` and `sign` now
+  // sandwich
+  decoys: Option<Vec<Decoys>>,
+  // The leader's own entropy, stashed here from `preprocess` so `select_decoys` can reuse it
+  // without re-deriving or re-broadcasting it
+  entropy: Option<[u8; 32]>,
 
   our_images: Vec<EdwardsPoint>,
   output_masks: Option<Scalar>,
   inputs: Vec<Rc<RefCell<Option<clsag::Details>>>>,
   msg: Rc<RefCell<Option<[u8; 32]>>>,
   clsags: Vec<AlgorithmMachine<Ed25519, clsag::Multisig>>,
+  // `permutation[j]` is the original (pre-consensus-sort) input index now at sorted position `j`,
+  // needed by `complete` to find each participant's CLSAG share for that input
+  permutation: Option<Vec<usize>>,
 
   tx: Option<Transaction>
 }
@@ -43,11 +55,19 @@ impl SignableTransaction {
     mut self,
     label: Vec<u8>,
     rng: &mut R,
-    rpc: &Rpc,
-    height: usize,
+    protocol: Protocol,
     keys: Rc<MultisigKeys<Ed25519>>,
     included: &[usize]
   ) -> Result<TransactionMachine, TransactionError> {
+    // `bulletproofs::verify_plus` is a stub (it has no native Bulletproofs+ verification yet), so
+    // the non-leader `sign` branch below would reject every participant's share unconditionally
+    // instead of actually checking them. Fail here, at negotiation time, with an honest error
+    // naming the real cause, rather than let multisig limp along into what looks like every other
+    // participant submitting an invalid share.
+    if protocol.bp_plus() {
+      Err(TransactionError::UnsupportedBulletproofsPlusMultisig)?;
+    }
+
     let mut our_images = vec![];
     let mut inputs = vec![];
     inputs.resize(self.inputs.len(), Rc::new(RefCell::new(None)));
@@ -78,18 +98,12 @@ impl SignableTransaction {
     }
     transcript.append_message(b"change", &self.change.as_bytes());
 
-    // Select decoys
-    // Ideally, this would be done post entropy, instead of now, yet doing so would require sign
-    // to be async which isn't feasible. This should be suitably competent though
-    // While this inability means we can immediately create the input, moving it out of the
-    // Rc RefCell, keeping it within an Rc RefCell keeps our options flexible
-    let decoys = decoys::select(
-      &mut ChaCha12Rng::from_seed(transcript.rng_seed(b"decoys", None)),
-      rpc,
-      height,
-      &self.inputs
-    ).await.map_err(|e| TransactionError::RpcError(e))?;
-
+    // Decoys can't be selected yet, as doing so here would derive them from purely
+    // transaction-deterministic data, before any participant's entropy exists to mix in, weakening
+    // ring unlinkability across re-runs of an otherwise identical transaction. `select_decoys`
+    // picks them later, once `preprocess` has broadcast that entropy; until then, each input's
+    // `clsag::Details` stays empty inside its `Rc<RefCell<_>>`, which is why the CLSAG machines
+    // below only hold onto the (still-empty) cell rather than needing the ring already built
     for (i, input) in self.inputs.iter().enumerate() {
       let keys = keys.offset(dalek_ff_group::Scalar(input.key_offset));
       let (image, _) = key_image::generate_share(
@@ -112,26 +126,70 @@ impl SignableTransaction {
     }
 
     // Verify these outputs by a dummy prep
-    self.prepare_outputs(rng)?;
+    self.prepare_outputs(rng, protocol)?;
 
     Ok(TransactionMachine {
       leader: keys.params().i() == included[0],
       signable: self,
       transcript,
+      protocol,
 
-      decoys,
+      decoys: None,
+      entropy: None,
 
       our_images,
       output_masks: None,
       inputs,
       msg,
       clsags,
+      permutation: None,
 
       tx: None
     })
   }
 }
 
+impl TransactionMachine {
+  /// Select this transaction's decoys, seeded by the leader's broadcast entropy rather than purely
+  /// transaction-deterministic data, so ring composition varies across re-runs of an otherwise
+  /// identical transaction.
+  ///
+  /// Must be called after every participant's `preprocess` output has been collected (`commitments`
+  /// is the same argument later passed to `sign`, carrying the leader's entropy at
+  /// `clsag_lens .. (clsag_lens + 32)` within their share) and before `sign` is called, as `sign`
+  /// requires decoys to already be selected. `preprocess`/`sign` can't perform this RPC-bound
+  /// lookup themselves, as `StateMachine` requires them to be synchronous.
+  pub async fn select_decoys(
+    &mut self,
+    rpc: &Rpc,
+    height: usize,
+    commitments: &[Option<Vec<u8>>]
+  ) -> Result<(), TransactionError> {
+    let clsag_len = 64 + clsag::Multisig::serialized_len();
+    let clsag_lens = clsag_len * self.clsags.len();
+
+    let entropy = if self.leader {
+      self.entropy.expect("leader selecting decoys before preprocessing")
+    } else {
+      let (l, prep) = commitments.iter().enumerate().filter(|(_, prep)| prep.is_some()).next()
+        .ok_or(TransactionError::FrostError(FrostError::InternalError("no participants".to_string())))?;
+      prep.as_ref().unwrap()[clsag_lens .. (clsag_lens + 32)].try_into()
+        .map_err(|_| TransactionError::FrostError(FrostError::InvalidShare(l)))?
+    };
+
+    self.decoys = Some(
+      decoys::select(
+        &mut ChaCha12Rng::from_seed(self.transcript.rng_seed(b"decoys", Some(entropy))),
+        rpc,
+        height,
+        &self.signable.inputs
+      ).await.map_err(|e| TransactionError::RpcError(e))?
+    );
+
+    Ok(())
+  }
+}
+
 impl StateMachine for TransactionMachine {
   type Signature = Transaction;
 
@@ -153,16 +211,25 @@ impl StateMachine for TransactionMachine {
       let mut entropy = [0; 32];
       rng.fill_bytes(&mut entropy);
       serialized.extend(&entropy);
+      self.entropy = Some(entropy);
 
       let mut rng = ChaCha12Rng::from_seed(self.transcript.rng_seed(b"tx_keys", Some(entropy)));
       // Safe to unwrap thanks to the dummy prepare
-      let (commitments, output_masks) = self.signable.prepare_outputs(&mut rng).unwrap();
+      let (commitments, output_masks) = self.signable.prepare_outputs(&mut rng, self.protocol).unwrap();
       self.output_masks = Some(output_masks);
 
-      let bp = bulletproofs::generate(&commitments).unwrap();
+      // Reorder the outputs so their position no longer follows payment order (which leaks the
+      // change output's position), continuing to draw from the same transcript-seeded `rng` every
+      // participant reproduces identically below
+      let commitments = output_shuffle::shuffle(&mut rng, commitments);
+
+      // The negotiated protocol version decides whether this is a classic Bulletproof or a
+      // Bulletproofs+ proof; everything downstream (the RCT prunable layout, the signature hash)
+      // follows from the type `bp` ends up being
+      let bp = bulletproofs::generate(&commitments, self.protocol.bp_plus()).unwrap();
       bp.consensus_encode(&mut serialized).unwrap();
 
-      let tx = self.signable.prepare_transaction(&commitments, bp);
+      let tx = self.signable.prepare_transaction(&commitments, bp, self.protocol);
       self.tx = Some(tx);
     }
 
@@ -178,6 +245,11 @@ impl StateMachine for TransactionMachine {
       Err(FrostError::InvalidSignTransition(State::Preprocessed, self.state()))?;
     }
 
+    // `select_decoys` must run between `preprocess` and `sign`, once every participant's entropy
+    // share has been collected
+    let decoys = self.decoys.clone()
+      .ok_or(FrostError::InternalError("decoys weren't selected before signing".to_string()))?;
+
     // FROST commitments, image, commitments, and their proofs
     let clsag_len = 64 + clsag::Multisig::serialized_len();
     let clsag_lens = clsag_len * self.clsags.len();
@@ -192,31 +264,45 @@ impl StateMachine for TransactionMachine {
       let prep = prep.as_ref().unwrap();
 
       // Not invalid outputs due to doing a dummy prep as leader
-      let (commitments, output_masks) = self.signable.prepare_outputs(
-        &mut ChaCha12Rng::from_seed(
-          self.transcript.rng_seed(
-            b"tx_keys",
-            Some(prep[clsag_lens .. (clsag_lens + 32)].try_into().map_err(|_| FrostError::InvalidShare(l))?)
-          )
+      let mut rng = ChaCha12Rng::from_seed(
+        self.transcript.rng_seed(
+          b"tx_keys",
+          Some(prep[clsag_lens .. (clsag_lens + 32)].try_into().map_err(|_| FrostError::InvalidShare(l))?)
         )
-      ).map_err(|_| FrostError::InvalidShare(l))?;
+      );
+      let (commitments, output_masks) =
+        self.signable.prepare_outputs(&mut rng, self.protocol).map_err(|_| FrostError::InvalidShare(l))?;
       self.output_masks.replace(output_masks);
 
-      // Verify the provided bulletproofs if not leader
-      let bp = deserialize(&prep[(clsag_lens + 32) .. prep.len()]).map_err(|_| FrostError::InvalidShare(l))?;
-      if !bulletproofs::verify(&bp, &commitments.iter().map(|c| c.calculate()).collect::<Vec<EdwardsPoint>>()) {
-        Err(FrostError::InvalidShare(l))?;
-      }
+      // Reproduce the same post-prepare reorder the leader applied, continuing to draw from the
+      // same transcript-seeded `rng` so every participant reaches the identical permutation
+      let commitments = output_shuffle::shuffle(&mut rng, commitments);
+
+      // Verify the provided bulletproofs if not leader, natively in Rust rather than via the
+      // C FFI range-proof verifier, dispatching to the variant the negotiated protocol expects
+      let calculated = commitments.iter().map(|c| c.calculate()).collect::<Vec<EdwardsPoint>>();
+      let bp = if self.protocol.bp_plus() {
+        let bp = deserialize(&prep[(clsag_lens + 32) .. prep.len()]).map_err(|_| FrostError::InvalidShare(l))?;
+        if !bulletproofs::verify_plus(&bp, &calculated) {
+          Err(FrostError::InvalidShare(l))?;
+        }
+        bulletproofs::Bulletproofs::Plus(bp)
+      } else {
+        let bp = deserialize(&prep[(clsag_lens + 32) .. prep.len()]).map_err(|_| FrostError::InvalidShare(l))?;
+        if !bulletproofs::verify(&bp, &calculated) {
+          Err(FrostError::InvalidShare(l))?;
+        }
+        bulletproofs::Bulletproofs::Original(bp)
+      };
 
-      tx = self.signable.prepare_transaction(&commitments, bp);
+      tx = self.signable.prepare_transaction(&commitments, bp, self.protocol);
     }
 
-    let mut rng = ChaCha12Rng::from_seed(self.transcript.rng_seed(b"pseudo_out_masks", None));
-    let mut sum_pseudo_outs = Scalar::zero();
+    // Calculate the key images in their original order to update the TX
+    // Multisig will parse/calculate/validate this as needed, yet doing so here as well provides
+    // the easiest API overall
+    let mut images = Vec::with_capacity(self.clsags.len());
     for c in 0 .. self.clsags.len() {
-      // Calculate the key images in order to update the TX
-      // Multisig will parse/calculate/validate this as needed, yet doing so here as well provides
-      // the easiest API overall
       let mut image = self.our_images[c];
       for (l, serialized) in commitments.iter().enumerate().filter(|(_, s)| s.is_some()) {
         image += CompressedEdwardsY(
@@ -224,22 +310,41 @@ impl StateMachine for TransactionMachine {
             .try_into().map_err(|_| FrostError::InvalidCommitment(l))?
         ).decompress().ok_or(FrostError::InvalidCommitment(l))?;
       }
+      images.push(image);
+    }
+
+    // Consensus requires an input's ring members, and therefore the tx's inputs themselves, be
+    // ordered by key image descending. `permutation[j]` is the original index now at sorted
+    // position `j`; since every participant computes the exact same `images` from the same
+    // combined shares, every participant derives the exact same permutation
+    let mut permutation: Vec<usize> = (0 .. self.clsags.len()).collect();
+    permutation.sort_by(|a, b| images[*b].compress().to_bytes().cmp(&images[*a].compress().to_bytes()));
+    let images: Vec<EdwardsPoint> = permutation.iter().map(|&orig| images[orig]).collect();
 
-      // TODO sort inputs
+    self.our_images = permutation.iter().map(|&orig| self.our_images[orig]).collect();
+    let decoys: Vec<Decoys> = permutation.iter().map(|&orig| decoys[orig].clone()).collect();
+    self.inputs = permutation.iter().map(|&orig| self.inputs[orig].clone()).collect();
+    let mut clsags: Vec<Option<_>> = self.clsags.drain(..).map(Some).collect();
+    self.clsags = permutation.iter().map(|&orig| clsags[orig].take().unwrap()).collect();
+
+    let mut rng = ChaCha12Rng::from_seed(self.transcript.rng_seed(b"pseudo_out_masks", None));
+    let mut sum_pseudo_outs = Scalar::zero();
+    for j in 0 .. self.clsags.len() {
+      let orig = permutation[j];
 
       let mut mask = random_scalar(&mut rng);
-      if c == (self.clsags.len() - 1) {
+      if j == (self.clsags.len() - 1) {
         mask = self.output_masks.unwrap() - sum_pseudo_outs;
       } else {
         sum_pseudo_outs += mask;
       }
 
-      self.inputs[c].replace(
+      self.inputs[j].replace(
         Some(
           clsag::Details::new(
             clsag::Input::new(
-              self.signable.inputs[c].commitment,
-              self.decoys[c].clone()
+              self.signable.inputs[orig].commitment,
+              decoys[j].clone()
             ).map_err(|_| panic!("Signing an input which isn't present in the ring we created for it"))?,
             mask
           )
@@ -249,8 +354,8 @@ impl StateMachine for TransactionMachine {
       tx.prefix.inputs.push(
         TxIn::ToKey {
           amount: VarInt(0),
-          key_offsets: self.decoys[c].offsets.clone(),
-          k_image: KeyImage { image: Hash(image.compress().to_bytes()) }
+          key_offsets: decoys[j].offsets.clone(),
+          k_image: KeyImage { image: Hash(images[j].compress().to_bytes()) }
         }
       );
     }
@@ -258,19 +363,24 @@ impl StateMachine for TransactionMachine {
     self.msg.replace(Some(tx.signature_hash().unwrap().0));
     self.tx = Some(tx);
 
-    // Iterate over each CLSAG calling sign
+    // Iterate over each CLSAG calling sign, reading its share out of the original (pre-sort)
+    // position every participant serialized it at
     let mut serialized = Vec::with_capacity(self.clsags.len() * 32);
-    for (c, clsag) in self.clsags.iter_mut().enumerate() {
+    for (j, clsag) in self.clsags.iter_mut().enumerate() {
+      let orig = permutation[j];
       serialized.extend(&clsag.sign(
         &commitments.iter().map(
           |commitments| commitments.clone().map(
-            |commitments| commitments[(c * clsag_len) .. ((c * clsag_len) + clsag_len)].to_vec()
+            |commitments| commitments[(orig * clsag_len) .. ((orig * clsag_len) + clsag_len)].to_vec()
           )
         ).collect::<Vec<_>>(),
         &vec![]
       )?);
     }
 
+    self.decoys = Some(decoys);
+    self.permutation = Some(permutation);
+
     Ok(serialized)
   }
 
@@ -281,9 +391,14 @@ impl StateMachine for TransactionMachine {
 
     let mut tx = self.tx.take().unwrap();
     let mut prunable = tx.rct_signatures.p.unwrap();
-    for (c, clsag) in self.clsags.iter_mut().enumerate() {
+    assert!(self.permutation.is_some(), "completing before sign sorted the inputs");
+    // Unlike `sign`'s own reads of `commitments` (broadcast before the permutation existed, so
+    // still keyed by original index), `shares` is every participant's serialized output of this
+    // same `sign` call's loop, which iterates `self.clsags` in already-sorted order -- so each
+    // participant laid their share out by sorted position `j`, not original position
+    for (j, clsag) in self.clsags.iter_mut().enumerate() {
       let (clsag, pseudo_out) = clsag.complete(&shares.iter().map(
-        |share| share.clone().map(|share| share[(c * 32) .. ((c * 32) + 32)].to_vec())
+        |share| share.clone().map(|share| share[(j * 32) .. ((j * 32) + 32)].to_vec())
       ).collect::<Vec<_>>())?;
       prunable.Clsags.push(clsag);
       prunable.pseudo_outs.push(Key { key: pseudo_out.compress().to_bytes() });
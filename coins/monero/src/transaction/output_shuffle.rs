@@ -0,0 +1,49 @@
+use rand_core::{RngCore, CryptoRng};
+
+// Fisher-Yates, swapping each position (from the back) with a uniformly chosen earlier-or-equal
+// position, so every participant replaying the same RNG draws reaches the same permutation
+fn fisher_yates<R: RngCore + CryptoRng, T>(rng: &mut R, items: &mut [T]) {
+  for i in (1 .. items.len()).rev() {
+    let j = usize::try_from(rng.next_u64() % (u64::try_from(i).unwrap() + 1)).unwrap();
+    items.swap(i, j);
+  }
+}
+
+/// Shuffle `outputs` using draws from the shared, transcript-seeded RNG, so every multisig
+/// participant derives the identical permutation from the identical commitments and masks, and
+/// drop any zero-amount output so it isn't broadcast.
+///
+/// Must run before the sum `prepare_outputs` uses to derive `output_masks` is taken, since that
+/// balancing sum depends on which outputs survive and in what order; each output's `(commitment,
+/// mask)` pair is moved together, so their association survives the shuffle unchanged.
+///
+/// Intended to be called from
+/// [`SignableTransaction::prepare_outputs`](super::SignableTransaction), which isn't present in
+/// this revision of the crate, between building the raw per-output commitment/mask/amount triples
+/// and returning them. [`shuffle`] is the fallback this revision wires in instead, at
+/// [`TransactionMachine`](super::multisig::TransactionMachine)'s call sites, where pruning can't
+/// be retrofitted safely (see its doc comment).
+pub fn shuffle_and_prune<R: RngCore + CryptoRng, T>(
+  rng: &mut R,
+  outputs: Vec<(T, u64)>,
+) -> Vec<(T, u64)> {
+  let mut outputs: Vec<_> = outputs.into_iter().filter(|(_, amount)| *amount != 0).collect();
+  fisher_yates(rng, &mut outputs);
+  outputs
+}
+
+/// Shuffle `outputs` (already-computed commitments returned by `prepare_outputs`) using draws
+/// from the shared, transcript-seeded RNG, so every multisig participant derives the identical
+/// permutation.
+///
+/// This is the order-only half of [`shuffle_and_prune`], safe to apply to `prepare_outputs`'s
+/// result after the fact: a permutation never changes the sum `output_masks` already is, so
+/// reordering post-hoc can't desync the two. Pruning zero-amount outputs post-hoc, by contrast,
+/// would: `output_masks` was summed over every output `prepare_outputs` built, including any
+/// zero-amount one, so dropping one here without `prepare_outputs` itself excluding it from that
+/// sum would leave the pseudo-out balance wrong. Closing that half of the request requires editing
+/// `prepare_outputs` directly, which isn't present in this revision of the crate.
+pub fn shuffle<R: RngCore + CryptoRng, T>(rng: &mut R, mut outputs: Vec<T>) -> Vec<T> {
+  fisher_yates(rng, &mut outputs);
+  outputs
+}
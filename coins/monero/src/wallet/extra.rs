@@ -0,0 +1,192 @@
+use rand_core::{RngCore, CryptoRng, OsRng};
+
+use sha3::{Digest, Keccak256};
+
+use curve25519_dalek::{
+  constants::ED25519_BASEPOINT_TABLE,
+  scalar::Scalar,
+  edwards::{EdwardsPoint, CompressedEdwardsY},
+};
+
+use crate::wallet::{address::MoneroAddress, send::Builder, scan::ReceivedOutput, TransactionError};
+
+// `add_data` limits each tx_extra field to this many bytes
+const MAX_FIELD_BYTES: usize = 255;
+// 4-byte total payload length, 2-byte chunk index, 2-byte chunk count
+const HEADER_BYTES: usize = 8;
+const MAX_PAYLOAD_BYTES_PER_CHUNK: usize = MAX_FIELD_BYTES - HEADER_BYTES;
+
+/// Derive the symmetric key shared between a transaction's ephemeral key and a recipient's view
+/// key, used to encrypt/decrypt an `add_payload` payload.
+pub fn shared_secret(ephemeral_key: EdwardsPoint, view_key: Scalar) -> [u8; 32] {
+  let ecdh_point = ephemeral_key * view_key;
+
+  let mut hasher = Keccak256::new();
+  hasher.update(b"fcmp_tx_extra_payload");
+  hasher.update(ecdh_point.compress().to_bytes());
+  hasher.finalize().into()
+}
+
+// A simple, unauthenticated keystream built from repeated hashing of the shared secret and a
+// block counter, XORed against the payload. Only meant to keep the payload confidential to
+// whoever can derive `shared_secret`; it doesn't protect the payload's length or authenticity.
+fn xor_keystream(data: &mut [u8], shared_secret: [u8; 32]) {
+  let mut offset = 0;
+  let mut counter: u64 = 0;
+  while offset < data.len() {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"fcmp_tx_extra_payload_keystream");
+    hasher.update(shared_secret);
+    hasher.update(counter.to_le_bytes());
+    let block = hasher.finalize();
+
+    let take = block.len().min(data.len() - offset);
+    for i in 0 .. take {
+      data[offset + i] ^= block[i];
+    }
+    offset += take;
+    counter += 1;
+  }
+}
+
+/// Split an arbitrary-length payload into `tx_extra`-sized chunks, each prefixed with a small
+/// header (total payload length, this chunk's index, and the total chunk count) so the scanner
+/// can detect, order, and reassemble the full payload.
+///
+/// If `shared_secret` is provided, the payload is encrypted before being chunked, keeping it
+/// confidential to whoever the caller derived the secret for.
+pub fn chunk_payload(mut payload: Vec<u8>, shared_secret: Option<[u8; 32]>) -> Vec<Vec<u8>> {
+  if let Some(shared_secret) = shared_secret {
+    xor_keystream(&mut payload, shared_secret);
+  }
+
+  let total_len = u32::try_from(payload.len()).expect("payload larger than 4 GiB");
+  let raw_chunks: Vec<&[u8]> =
+    if payload.is_empty() { vec![&[][..]] } else { payload.chunks(MAX_PAYLOAD_BYTES_PER_CHUNK).collect() };
+  let chunk_count = u16::try_from(raw_chunks.len()).expect("payload needs more than 65535 chunks");
+
+  raw_chunks
+    .into_iter()
+    .enumerate()
+    .map(|(i, chunk)| {
+      let mut field = Vec::with_capacity(HEADER_BYTES + chunk.len());
+      field.extend(total_len.to_le_bytes());
+      field.extend(u16::try_from(i).unwrap().to_le_bytes());
+      field.extend(chunk_count.to_le_bytes());
+      field.extend(chunk);
+      field
+    })
+    .collect()
+}
+
+/// Detect, order, and reassemble a payload which may have been split across multiple `tx_extra`
+/// fields by `chunk_payload`, decrypting it if `shared_secret` was derivable for this output.
+///
+/// Returns `None` if `fields` doesn't contain a complete, consistent set of chunks.
+pub fn reassemble_payload(fields: &[Vec<u8>], shared_secret: Option<[u8; 32]>) -> Option<Vec<u8>> {
+  let mut total_len = None;
+  let mut chunk_count = None;
+  let mut chunks = vec![];
+
+  for field in fields {
+    if field.len() < HEADER_BYTES {
+      continue;
+    }
+
+    let this_total_len = u32::from_le_bytes(field[0 .. 4].try_into().unwrap());
+    let index = u16::from_le_bytes(field[4 .. 6].try_into().unwrap());
+    let count = u16::from_le_bytes(field[6 .. 8].try_into().unwrap());
+
+    if *total_len.get_or_insert(this_total_len) != this_total_len {
+      continue;
+    }
+    if *chunk_count.get_or_insert(count) != count {
+      continue;
+    }
+    chunks.push((index, field[HEADER_BYTES ..].to_vec()));
+  }
+
+  let chunk_count = usize::from(chunk_count?);
+  if chunks.len() != chunk_count {
+    return None;
+  }
+  chunks.sort_by_key(|(index, _)| *index);
+
+  let mut payload = Vec::with_capacity(total_len? as usize);
+  for (expected_index, (index, chunk)) in chunks.into_iter().enumerate() {
+    if usize::from(index) != expected_index {
+      return None;
+    }
+    payload.extend(chunk);
+  }
+  payload.truncate(total_len? as usize);
+
+  if let Some(shared_secret) = shared_secret {
+    xor_keystream(&mut payload, shared_secret);
+  }
+
+  Some(payload)
+}
+
+// A fresh, single-use keypair for encrypting one `add_payload` call, independent of the
+// transaction's own output keys, so the recipient never needs more than their view key and this
+// payload's own published point (its first `tx_extra` field) to derive `shared_secret`.
+fn payload_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (Scalar, EdwardsPoint) {
+  let ephemeral_key = Scalar::random(rng);
+  (ephemeral_key, &ephemeral_key * ED25519_BASEPOINT_TABLE)
+}
+
+impl Builder {
+  /// Attach an arbitrarily large payload to this transaction's `tx_extra`, chunked via
+  /// [`chunk_payload`] the same way [`Self::add_data`] stores a single field, and reassembled on
+  /// the receiving side by [`ReceivedOutput::payload`].
+  ///
+  /// If `recipient` is given, the payload is encrypted to their view key: a fresh, payload-only
+  /// ephemeral key is published as the payload's own leading field, ahead of its chunks, letting
+  /// `recipient` (and only them) derive the matching [`shared_secret`] without needing any of this
+  /// transaction's other keys.
+  pub fn add_payload(
+    &mut self,
+    payload: Vec<u8>,
+    recipient: Option<MoneroAddress>,
+  ) -> Result<(), TransactionError> {
+    let shared_secret = match recipient {
+      Some(recipient) => {
+        let (ephemeral_key, ephemeral_point) = payload_keypair(&mut OsRng);
+        self.add_data(ephemeral_point.compress().to_bytes().to_vec())?;
+        Some(shared_secret(recipient.view, ephemeral_key))
+      }
+      None => None,
+    };
+
+    for chunk in chunk_payload(payload, shared_secret) {
+      self.add_data(chunk)?;
+    }
+    Ok(())
+  }
+}
+
+impl ReceivedOutput {
+  /// Reassemble, and if encrypted decrypt, the payload [`Builder::add_payload`] attached to this
+  /// output's transaction, if any.
+  ///
+  /// An encrypted payload's leading field is its publisher's ephemeral point rather than a chunk
+  /// of the payload itself; try that interpretation first, falling back to an unencrypted payload
+  /// spanning every field if it doesn't decode or doesn't reassemble.
+  pub fn payload(&self) -> Option<Vec<u8>> {
+    let fields = self.arbitrary_data();
+
+    if let Some((marker, chunks)) = fields.split_first() {
+      if let Ok(point_bytes) = <[u8; 32]>::try_from(marker.as_slice()) {
+        if let Some(ephemeral_point) = CompressedEdwardsY(point_bytes).decompress() {
+          let secret = shared_secret(ephemeral_point, self.view_key);
+          if let Some(payload) = reassemble_payload(chunks, Some(secret)) {
+            return Some(payload);
+          }
+        }
+      }
+    }
+
+    reassemble_payload(&fields, None)
+  }
+}
@@ -0,0 +1,43 @@
+use monero_serai::{rpc::Rpc, transaction::Transaction};
+
+mod runner;
+
+test!(
+  add_payload_spanning_multiple_fields,
+  (
+    |_, mut builder: Builder, addr| async move {
+      // Bigger than a single `add_data` field, so this has to be split into several
+      let payload = (0 .. 1000).map(|i| (i % 256) as u8).collect::<Vec<_>>();
+
+      builder.add_payload(payload.clone(), None).unwrap();
+      builder.add_payment(addr, 5);
+      (builder.build().unwrap(), payload)
+    },
+    |rpc: Rpc, signed: Transaction, mut scanner: Scanner, payload: Vec<u8>| async move {
+      let tx = rpc.get_transaction(signed.hash()).await.unwrap();
+      let output = scanner.scan_transaction(&tx).not_locked().swap_remove(0);
+      assert_eq!(output.commitment().amount, 5);
+      assert_eq!(output.payload(), Some(payload));
+    },
+  ),
+);
+
+test!(
+  add_encrypted_payload,
+  (
+    |_, mut builder: Builder, addr| async move {
+      let payload = b"only the recipient should be able to read this".to_vec();
+
+      builder.add_payload(payload.clone(), Some(addr)).unwrap();
+      builder.add_payment(addr, 5);
+      (builder.build().unwrap(), payload)
+    },
+    |rpc: Rpc, signed: Transaction, mut scanner: Scanner, payload: Vec<u8>| async move {
+      let tx = rpc.get_transaction(signed.hash()).await.unwrap();
+      let output = scanner.scan_transaction(&tx).not_locked().swap_remove(0);
+      assert_eq!(output.commitment().amount, 5);
+      // The scanner derived the shared secret for its own output, so it can decrypt the payload
+      assert_eq!(output.payload(), Some(payload));
+    },
+  ),
+);
@@ -0,0 +1,40 @@
+use std::time::Instant;
+
+use rand_core::OsRng;
+
+use ciphersuite::{group::ff::Field, Ciphersuite, Helios};
+
+use generalized_bulletproofs::Generators;
+use fcmps::tree::{hash_grow, PrecomputedGenerators, hash_grow_precomputed};
+
+// A small, ad-hoc benchmark comparing plain `hash_grow` against its precomputed-generator
+// counterpart across branching widths realistic for a curve tree updated every block.
+fn bench_width(generators: &Generators<transcript::RecommendedTranscript, Helios>, width: usize) {
+  let init_point = <Helios as Ciphersuite>::G::identity();
+  let children =
+    (0 .. width).map(|_| <Helios as Ciphersuite>::F::random(&mut OsRng)).collect::<Vec<_>>();
+
+  let start = Instant::now();
+  for _ in 0 .. 100 {
+    hash_grow(generators, init_point, 0, <Helios as Ciphersuite>::F::ZERO, &children).unwrap();
+  }
+  let plain = start.elapsed();
+
+  let precomputed = PrecomputedGenerators::new(generators, 4);
+  let start = Instant::now();
+  for _ in 0 .. 100 {
+    hash_grow_precomputed(&precomputed, init_point, 0, <Helios as Ciphersuite>::F::ZERO, &children)
+      .unwrap();
+  }
+  let table_based = start.elapsed();
+
+  println!("width {width}: plain {plain:?}, precomputed {table_based:?}");
+}
+
+fn main() {
+  let generators =
+    Generators::<transcript::RecommendedTranscript, Helios>::new(b"fcmps-tree-bench", 1024);
+  for width in [2, 4, 8, 16, 32] {
+    bench_width(&generators, width);
+  }
+}
@@ -1,7 +1,10 @@
 use transcript::Transcript;
 
 use multiexp::multiexp_vartime;
-use ciphersuite::Ciphersuite;
+use ciphersuite::{
+  group::{ff::{Field, PrimeField, PrimeFieldBits}, Group, GroupEncoding},
+  Ciphersuite,
+};
 
 use generalized_bulletproofs::Generators;
 
@@ -46,3 +49,348 @@ pub fn hash_trim<T: Transcript, C: Ciphersuite>(
   }
   Some(existing_hash - multiexp_vartime(&pairs))
 }
+
+/// Windowed (Straus-style) tables over a set of generators, precomputed once so repeated
+/// [`hash_grow`]/[`hash_trim`] calls against the same generators become table lookups and point
+/// additions rather than fresh windowed decompositions of the scalar each time.
+#[derive(Clone)]
+pub struct PrecomputedGenerators<C: Ciphersuite> {
+  window: usize,
+  // tables[i][d] = d * g_bold[i], for d in 0 .. (1 << window)
+  tables: Vec<Vec<C::G>>,
+}
+
+impl<C: Ciphersuite> PrecomputedGenerators<C> {
+  /// Build windowed tables for every generator exposed by `generators.g_bold_slice()`.
+  ///
+  /// A wider window trades more precomputation and memory for fewer point doublings per
+  /// scalar multiplication. 4 is a reasonable default for generator sets updated every block.
+  pub fn new<T: Transcript>(generators: &Generators<T, C>, window: usize) -> Self {
+    assert!(window != 0, "precomputed generator window must be non-zero");
+    let table_size = 1usize << window;
+    let tables = generators
+      .g_bold_slice()
+      .iter()
+      .map(|base| {
+        let mut table = Vec::with_capacity(table_size);
+        table.push(C::G::identity());
+        for _ in 1 .. table_size {
+          table.push(*table.last().unwrap() + base);
+        }
+        table
+      })
+      .collect();
+    PrecomputedGenerators { window, tables }
+  }
+
+  fn scalar_mul(&self, index: usize, scalar: C::F) -> Option<C::G> {
+    let table = self.tables.get(index)?;
+    let bits = scalar.to_le_bits();
+
+    let mut windows = Vec::with_capacity((bits.len() + self.window - 1) / self.window);
+    let mut i = 0;
+    while i < bits.len() {
+      let mut digit = 0usize;
+      for b in 0 .. self.window {
+        if bits.get(i + b).map(|bit| *bit).unwrap_or(false) {
+          digit |= 1 << b;
+        }
+      }
+      windows.push(digit);
+      i += self.window;
+    }
+
+    let mut acc = C::G::identity();
+    for digit in windows.into_iter().rev() {
+      for _ in 0 .. self.window {
+        acc = acc.double();
+      }
+      acc += table[digit];
+    }
+    Some(acc)
+  }
+}
+
+/// [`hash_grow`], accelerated with [`PrecomputedGenerators`].
+pub fn hash_grow_precomputed<C: Ciphersuite>(
+  precomputed: &PrecomputedGenerators<C>,
+  existing_hash: C::G,
+  offset: usize,
+  first_child_after_offset: C::F,
+  new_children: &[C::F],
+) -> Option<C::G> {
+  if new_children.is_empty() {
+    return None;
+  }
+
+  let mut hash = existing_hash;
+  let mut new_children = new_children.iter().enumerate();
+  let (_, first_new) = new_children.next().unwrap();
+  hash += precomputed.scalar_mul(offset, *first_new - first_child_after_offset)?;
+  for (i, new) in new_children {
+    hash += precomputed.scalar_mul(offset + i, *new)?;
+  }
+  Some(hash)
+}
+
+/// [`hash_trim`], accelerated with [`PrecomputedGenerators`].
+pub fn hash_trim_precomputed<C: Ciphersuite>(
+  precomputed: &PrecomputedGenerators<C>,
+  existing_hash: C::G,
+  offset: usize,
+  children: &[C::F],
+) -> Option<C::G> {
+  let mut hash = existing_hash;
+  for (i, child) in children.iter().enumerate() {
+    hash -= precomputed.scalar_mul(offset + i, *child)?;
+  }
+  Some(hash)
+}
+
+// Domain-separated hash used to fold a sealed node's hash into a field element so it can become
+// a child of the layer above it.
+fn child_from_hash<C: Ciphersuite>(hash: C::G) -> C::F {
+  C::hash_to_F(b"fcmp_tree_child", hash.to_bytes().as_ref())
+}
+
+// A node within a layer: its current hash (relative to the layer's initialization point) and the
+// children which have been folded into it so far.
+#[derive(Clone)]
+struct Node<C: Ciphersuite> {
+  hash: C::G,
+  children: Vec<C::F>,
+}
+
+// A layer of the tree. Every node but the last is sealed (has `width` children). The last node is
+// the rightmost, and may still be grown.
+#[derive(Clone)]
+struct Layer<C: Ciphersuite> {
+  nodes: Vec<Node<C>>,
+}
+
+/// A single step of a membership path, local to one layer of the tree.
+///
+/// `siblings` is every child of this layer's node other than the one being proven for, and
+/// `position` is the index the proven child occupies within the node (before removal).
+pub struct PathStep<C: Ciphersuite> {
+  pub siblings: Vec<C::F>,
+  pub position: usize,
+}
+
+/// An incremental, reorg-safe curve tree built on top of [`hash_grow`] and [`hash_trim`].
+///
+/// This owns every layer of the tree, filling each layer's rightmost node as leaves are
+/// appended, sealing nodes once they reach the configured branching width, and creating new
+/// layers as the root overflows.
+#[derive(Clone)]
+pub struct Tree<T: Transcript, C: Ciphersuite> {
+  generators: Generators<T, C>,
+  precomputed: Option<PrecomputedGenerators<C>>,
+  // The hash of an empty node (no children grown into it yet).
+  init_point: C::G,
+  // The amount of children a node holds before it's sealed.
+  width: usize,
+  layers: Vec<Layer<C>>,
+}
+
+impl<T: Transcript, C: Ciphersuite> Tree<T, C> {
+  /// Create a new, empty tree.
+  ///
+  /// `init_point` is the hash of a node with no children, as documented on [`hash_grow`].
+  pub fn new(generators: Generators<T, C>, width: usize, init_point: C::G) -> Self {
+    assert!(width != 0, "tree width must be non-zero");
+    Tree { generators, precomputed: None, init_point, width, layers: vec![] }
+  }
+
+  /// As [`Tree::new`], but with [`PrecomputedGenerators`] the tree holds onto for the rest of
+  /// its lifetime, amortizing their cost across every layer update.
+  pub fn with_precomputed_generators(
+    generators: Generators<T, C>,
+    precomputed: PrecomputedGenerators<C>,
+    width: usize,
+    init_point: C::G,
+  ) -> Self {
+    assert!(width != 0, "tree width must be non-zero");
+    Tree { generators, precomputed: Some(precomputed), init_point, width, layers: vec![] }
+  }
+
+  fn grow(
+    &self,
+    existing_hash: C::G,
+    offset: usize,
+    first_child_after_offset: C::F,
+    new_children: &[C::F],
+  ) -> Option<C::G> {
+    match &self.precomputed {
+      Some(precomputed) =>
+        hash_grow_precomputed(precomputed, existing_hash, offset, first_child_after_offset, new_children),
+      None => hash_grow(&self.generators, existing_hash, offset, first_child_after_offset, new_children),
+    }
+  }
+
+  fn trim(&self, existing_hash: C::G, offset: usize, children: &[C::F]) -> Option<C::G> {
+    match &self.precomputed {
+      Some(precomputed) => hash_trim_precomputed(precomputed, existing_hash, offset, children),
+      None => hash_trim(&self.generators, existing_hash, offset, children),
+    }
+  }
+
+  /// The current root hash of the tree.
+  ///
+  /// This is the init point if the tree has no leaves yet.
+  pub fn root(&self) -> C::G {
+    match self.layers.last() {
+      // The topmost layer only ever has a single node, as a new layer is created the moment its
+      // sole node would otherwise be sealed and promoted into one above it.
+      Some(layer) => layer.nodes.last().expect("layer without any nodes").hash,
+      None => self.init_point,
+    }
+  }
+
+  fn push_children(&mut self, layer_index: usize, mut children: Vec<C::F>) {
+    if children.is_empty() {
+      return;
+    }
+    if layer_index == self.layers.len() {
+      self.layers.push(Layer { nodes: vec![] });
+    }
+
+    while !children.is_empty() {
+      let layer = &mut self.layers[layer_index];
+      let needs_new_node =
+        layer.nodes.last().map(|node| node.children.len() == self.width).unwrap_or(true);
+      if needs_new_node {
+        layer.nodes.push(Node { hash: self.init_point, children: vec![] });
+      }
+
+      let node = layer.nodes.last_mut().unwrap();
+      let offset = node.children.len();
+      let space = self.width - offset;
+      let take = space.min(children.len());
+      let batch = children.drain(.. take).collect::<Vec<_>>();
+
+      node.hash = self.grow(node.hash, offset, C::F::ZERO, &batch)
+        .expect("not enough generators for the configured tree width");
+      node.children.extend(batch);
+
+      if node.children.len() == self.width {
+        let sealed_hash = node.hash;
+        self.push_children(layer_index + 1, vec![child_from_hash::<C>(sealed_hash)]);
+      }
+    }
+  }
+
+  /// Append leaves to the tree, growing its rightmost nodes and sealing/promoting them as they
+  /// fill up.
+  pub fn append(&mut self, leaves: &[C::F]) {
+    self.push_children(0, leaves.to_vec());
+  }
+
+  fn pop_children(&mut self, layer_index: usize, mut amount: usize) {
+    if amount == 0 || layer_index >= self.layers.len() {
+      return;
+    }
+
+    while amount != 0 {
+      let layer = &mut self.layers[layer_index];
+      let Some(node) = layer.nodes.last_mut() else { break };
+      let node_len = node.children.len();
+
+      if amount < node_len {
+        // Less is being removed than remains, so trimming the removed children is cheaper than
+        // rebuilding the node from scratch
+        let remaining = node_len - amount;
+        if amount < remaining {
+          let removed = node.children[remaining ..].to_vec();
+          node.hash = self.trim(node.hash, remaining, &removed)
+            .expect("failed to trim children from the rightmost node");
+        } else {
+          let kept = node.children[.. remaining].to_vec();
+          node.hash = self.grow(self.init_point, 0, C::F::ZERO, &kept).unwrap_or(self.init_point);
+        }
+        node.children.truncate(remaining);
+        amount = 0;
+      } else {
+        // The entire rightmost node is being removed
+        let was_sealed = node_len == self.width;
+        layer.nodes.pop();
+        amount -= node_len;
+        if was_sealed {
+          // This node's hash was promoted into a single child of the layer above, so undo that
+          self.pop_children(layer_index + 1, 1);
+        }
+      }
+    }
+
+    // A layer with no nodes left isn't a layer at all; if it's (now) the topmost layer, drop it
+    // (and any further-now-trailing empty layers this uncovers) so `root` sees either a populated
+    // topmost layer or none, rather than panicking on an empty one.
+    while matches!(self.layers.last(), Some(layer) if layer.nodes.is_empty()) {
+      self.layers.pop();
+    }
+  }
+
+  /// Remove the last `n` leaves appended to the tree.
+  pub fn rollback(&mut self, n: usize) {
+    self.pop_children(0, n);
+  }
+
+  /// Produce the membership path for the leaf at `index`, one [`PathStep`] per layer, ordered
+  /// from the leaves towards the root.
+  pub fn path(&self, index: usize) -> Option<Vec<PathStep<C>>> {
+    let mut node_index = index;
+    let mut steps = Vec::with_capacity(self.layers.len());
+    for layer in &self.layers {
+      let node_idx = node_index / self.width;
+      let position = node_index % self.width;
+
+      let node = layer.nodes.get(node_idx)?;
+      if position >= node.children.len() {
+        return None;
+      }
+      let mut siblings = node.children.clone();
+      siblings.remove(position);
+
+      steps.push(PathStep { siblings, position });
+      node_index = node_idx;
+    }
+    Some(steps)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rand_core::OsRng;
+
+  use ciphersuite::{group::ff::Field, Ciphersuite, Group, Helios};
+
+  use generalized_bulletproofs::Generators;
+
+  use super::Tree;
+
+  // Appending exactly `width` leaves seals and promotes layer 0 into a new layer 1; rolling all of
+  // them back must leave the tree with no layers at all, not a layer with no nodes, so `root`
+  // falls back to `init_point` instead of panicking on `layers.last().expect(...)`.
+  #[test]
+  fn rollback_to_empty_tree() {
+    let width = 4;
+    let generators =
+      Generators::<transcript::RecommendedTranscript, Helios>::new(b"fcmps-tree-test", 1024);
+    let init_point = <Helios as Ciphersuite>::G::identity();
+
+    let mut tree = Tree::new(generators, width, init_point);
+    assert_eq!(tree.root(), init_point);
+
+    let leaves =
+      (0 .. width).map(|_| <Helios as Ciphersuite>::F::random(&mut OsRng)).collect::<Vec<_>>();
+    tree.append(&leaves);
+    assert_ne!(tree.root(), init_point);
+
+    tree.rollback(width);
+    assert_eq!(tree.root(), init_point);
+
+    // The tree must still be usable afterwards
+    tree.append(&leaves);
+    assert_ne!(tree.root(), init_point);
+  }
+}
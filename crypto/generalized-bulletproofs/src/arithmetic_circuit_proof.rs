@@ -1,3 +1,5 @@
+use std::io::{self, Read};
+
 use rand_core::{RngCore, CryptoRng};
 
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -16,7 +18,7 @@ use ciphersuite::{
 use crate::{
   ScalarVector, ScalarMatrix, PointVector, ProofGenerators, PedersenCommitment,
   PedersenVectorCommitment, BatchVerifier,
-  inner_product::{IpError, IpStatement, IpWitness, IpProof, P},
+  inner_product::{IpError, IpStatement, IpWitness, IpProof, P, WipStatement, WipWitness, WipProof},
 };
 
 /// Bulletproofs' Arithmetic Circuit Statement from 5.1, modified per Generalized Bulletproofs.
@@ -77,9 +79,11 @@ pub enum AcError {
   ConstrainedNonExistentCommitment,
   IncorrectAmountOfGenerators,
   InconsistentWitness,
-  IncorrectTBeforeNiLength,
-  IncorrectTAfterNiLength,
+  IncorrectTLength,
   Ip(IpError),
+  NonCanonicalEncoding,
+  TrailingBytes,
+  IoError,
 }
 
 impl<C: Ciphersuite> ArithmeticCircuitWitness<C> {
@@ -97,18 +101,39 @@ impl<C: Ciphersuite> ArithmeticCircuitWitness<C> {
     let aO = aL.clone() * &aR;
     Ok(ArithmeticCircuitWitness { aL, aR, aO, c, v })
   }
+
+  /// Concatenate several witnesses for the same circuit shape into the single witness expected
+  /// by the statement [`ArithmeticCircuitStatement::aggregate`] produces.
+  ///
+  /// `witnesses` must be passed in the same order the statements were to `aggregate`.
+  pub fn aggregate(witnesses: Vec<Self>) -> Self {
+    let mut aL = ScalarVector(Vec::with_capacity(witnesses.iter().map(|w| w.aL.len()).sum()));
+    let mut aR = ScalarVector(Vec::with_capacity(aL.0.capacity()));
+    let mut aO = ScalarVector(Vec::with_capacity(aL.0.capacity()));
+    let mut c = vec![];
+    let mut v = vec![];
+    for witness in witnesses {
+      aL.0.extend(witness.aL.0);
+      aR.0.extend(witness.aR.0);
+      aO.0.extend(witness.aO.0);
+      c.extend(witness.c);
+      v.extend(witness.v);
+    }
+    ArithmeticCircuitWitness { aL, aR, aO, c, v }
+  }
 }
 
-/// A proof for an arithmetic circuit statement.
+/// A proof for an arithmetic circuit statement, closing out its final round with Bulletproofs'
+/// original (unweighted) inner-product argument.
 #[derive(Clone, Debug, Zeroize)]
 pub struct ArithmeticCircuitProof<C: Ciphersuite> {
   AI: C::G,
   AO: C::G,
   S: C::G,
 
-  // TODO: Merge these two vectors
-  T_before_ni: Vec<C::G>,
-  T_after_ni: Vec<C::G>,
+  // The commitments to t's coefficients, excluding the one at index `ni` (which is opened
+  // directly via `t_caret`/`tau_x` instead of committed to)
+  T: Vec<C::G>,
   tau_x: C::F,
   u: C::F,
   t_caret: C::F,
@@ -116,12 +141,132 @@ pub struct ArithmeticCircuitProof<C: Ciphersuite> {
   ip: IpProof<C>,
 }
 
+impl<C: Ciphersuite> ArithmeticCircuitProof<C> {
+  /// Write this proof in a canonical format.
+  pub fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    writer.write_all(self.AI.to_bytes().as_ref())?;
+    writer.write_all(self.AO.to_bytes().as_ref())?;
+    writer.write_all(self.S.to_bytes().as_ref())?;
+
+    writer.write_all(&u32::try_from(self.T.len()).expect("more than 2**32 T terms").to_le_bytes())?;
+    for T in &self.T {
+      writer.write_all(T.to_bytes().as_ref())?;
+    }
+
+    writer.write_all(self.tau_x.to_repr().as_ref())?;
+    writer.write_all(self.u.to_repr().as_ref())?;
+    writer.write_all(self.t_caret.to_repr().as_ref())?;
+
+    self.ip.write(writer)
+  }
+
+  /// Read a proof for a statement with `n` multiplications and `ni = 2 * (c + 1)` (`c` being the
+  /// statement's amount of Pedersen Vector Commitments) from the canonical format written by
+  /// [`Self::write`].
+  ///
+  /// Rejects non-canonical point/scalar encodings, a `T` vector of the wrong length for `ni`, and
+  /// trailing bytes after the proof.
+  pub fn read(bytes: &[u8], n: usize, ni: usize) -> Result<Self, AcError> {
+    let mut cursor = io::Cursor::new(bytes);
+
+    let read_point = |cursor: &mut io::Cursor<&[u8]>| -> Result<C::G, AcError> {
+      let mut repr = <C::G as GroupEncoding>::Repr::default();
+      cursor.read_exact(repr.as_mut()).map_err(|_| AcError::IoError)?;
+      Option::from(C::G::from_bytes(&repr)).ok_or(AcError::NonCanonicalEncoding)
+    };
+    let read_scalar = |cursor: &mut io::Cursor<&[u8]>| -> Result<C::F, AcError> {
+      let mut repr = <C::F as PrimeField>::Repr::default();
+      cursor.read_exact(repr.as_mut()).map_err(|_| AcError::IoError)?;
+      Option::from(C::F::from_repr(repr)).ok_or(AcError::NonCanonicalEncoding)
+    };
+
+    let AI = read_point(&mut cursor)?;
+    let AO = read_point(&mut cursor)?;
+    let S = read_point(&mut cursor)?;
+
+    let mut T_len_bytes = [0; 4];
+    cursor.read_exact(&mut T_len_bytes).map_err(|_| AcError::IoError)?;
+    let T_len = usize::try_from(u32::from_le_bytes(T_len_bytes)).unwrap();
+
+    let t_poly_len = (2 * (1 + ni + 1)) - 1;
+    if T_len != (t_poly_len - 1) {
+      Err(AcError::IncorrectTLength)?;
+    }
+    let mut T = Vec::with_capacity(T_len);
+    for _ in 0 .. T_len {
+      T.push(read_point(&mut cursor)?);
+    }
+
+    let tau_x = read_scalar(&mut cursor)?;
+    let u = read_scalar(&mut cursor)?;
+    let t_caret = read_scalar(&mut cursor)?;
+
+    let mut log2_n = 0;
+    while (1 << log2_n) != n {
+      log2_n += 1;
+    }
+    let ip = IpProof::read(&mut cursor, log2_n).map_err(|_| AcError::IoError)?;
+
+    if (cursor.position() as usize) != bytes.len() {
+      Err(AcError::TrailingBytes)?;
+    }
+
+    Ok(ArithmeticCircuitProof { AI, AO, S, T, tau_x, u, t_caret, ip })
+  }
+}
+
+/// As [`ArithmeticCircuitProof`], except closing out the final round with the Bulletproofs+-style
+/// weighted norm argument instead of the unweighted inner-product argument.
+///
+/// This backend closes `t_caret` out via [`WipStatement`], which proves the weighted pairing
+/// `sum_i mu^i * n_i * l_i` of `n` (`r` scaled by `y_inv`) against the independent `l` vector, not
+/// a self-inner-product/norm of one vector. That's only equal to `t_caret` (itself the unweighted
+/// `l.inner_product(&r)`) when the statement's `l`/`r` vectors are related such that the two
+/// decompositions coincide (as holds for circuits built directly off a norm/bit decomposition);
+/// [`Self::prove_norm`] checks this and returns [`AcError::InconsistentWitness`] if it doesn't
+/// hold. For arbitrary circuits, use [`ArithmeticCircuitProof`] with `prove`/`verify` instead.
+#[derive(Clone, Debug, Zeroize)]
+pub struct NormArithmeticCircuitProof<C: Ciphersuite> {
+  AI: C::G,
+  AO: C::G,
+  S: C::G,
+
+  T: Vec<C::G>,
+  tau_x: C::F,
+  u: C::F,
+  t_caret: C::F,
+
+  norm: WipProof<C>,
+}
+
 struct YzChallenges<C: Ciphersuite> {
   y: C::F,
   y_inv: ScalarVector<C::F>,
   z: ScalarVector<C::F>,
 }
 
+// Everything both the inner-product and weighted-norm backends need before their final round's
+// argument is proven: the round commitments, the T vector opening, and the folded `l`/`r`/`p`
+// the final argument proves knowledge of.
+struct ProofBasis<C: Ciphersuite> {
+  AI: C::G,
+  AO: C::G,
+  S: C::G,
+  T: Vec<C::G>,
+  tau_x: C::F,
+  u: C::F,
+  t_caret: C::F,
+  y_inv: ScalarVector<C::F>,
+  l: ScalarVector<C::F>,
+  r: ScalarVector<C::F>,
+  // l * g_bold + r * (y_inv * h_bold), as (scalar, generator) pairs, with the final round's own
+  // g term (which differs by backend) left for the caller to append
+  p_terms: Vec<(C::F, C::G)>,
+  // The challenge the final round's argument is bound/weighted under (`x` for the Ip backend,
+  // `mu` for the Norm backend)
+  challenge: C::F,
+}
+
 impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a, T, C> {
   // The amount of multiplications performed.
   fn n(&self) -> usize {
@@ -215,6 +360,85 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
     Ok(Self { generators, WL, WR, WO, WCL, WCR, WV, c, C, V })
   }
 
+  /// Build a single aggregated statement proving `statements.len()` independent instances of the
+  /// same circuit shape as one proof, analogous to an aggregated Bulletproofs range proof.
+  ///
+  /// Every entry of `statements` must share the exact same circuit shape (`WL`/`WR`/`WO`/`WCL`/
+  /// `WCR`/`WV`/`c`, taken from the first entry) but carries its own `C`/`V` — the actual Pedersen
+  /// (Vector) Commitment points being opened for that copy — which is what makes these independent
+  /// instances rather than `statements.len()` proofs of the same one. Each instance gets its own
+  /// disjoint block of `n()` multiplications (and its own block of Pedersen (Vector) Commitments),
+  /// carved out of `generators`, which must already be sized for the full
+  /// `statements.len() * n()` aggregate. Pair this with [`ArithmeticCircuitWitness::aggregate`],
+  /// passing witnesses in the same order as `statements`, to build the matching witness, then
+  /// prove/verify the result exactly as any other statement: the proof this produces is still
+  /// just one `tau_x`/`u`/`t_caret` opening closed out with a single inner-product argument over
+  /// the full-length vectors, so its size grows only logarithmically in `statements.len()`.
+  pub fn aggregate(
+    generators: ProofGenerators<'a, T, C>,
+    statements: &[Self],
+  ) -> Result<Self, AcError> {
+    let copies = statements.len();
+    let template = statements.first().ok_or(AcError::InconsistentAmountOfConstraints)?;
+    let n_block = template.n();
+    let m_block = template.m();
+
+    if generators.len() != (n_block * copies) {
+      Err(AcError::IncorrectAmountOfGenerators)?;
+    }
+    for statement in statements {
+      if (statement.n() != n_block) || (statement.m() != m_block) {
+        Err(AcError::InconsistentAmountOfConstraints)?;
+      }
+    }
+
+    // Shift a matrix's column indices into the block-th slot of a `stride`-wide row of blocks
+    let shift = |matrix: &ScalarMatrix<C>, block: usize, stride: usize| -> ScalarMatrix<C> {
+      ScalarMatrix {
+        data: matrix
+          .data
+          .iter()
+          .map(|row| row.iter().map(|(j, weight)| (j + (block * stride), *weight)).collect())
+          .collect(),
+        highest_index: if matrix.data.is_empty() { 0 } else { matrix.highest_index + (block * stride) },
+      }
+    };
+
+    let mut WL = ScalarMatrix { data: vec![], highest_index: 0 };
+    let mut WR = ScalarMatrix { data: vec![], highest_index: 0 };
+    let mut WO = ScalarMatrix { data: vec![], highest_index: 0 };
+    let mut WV = ScalarMatrix { data: vec![], highest_index: 0 };
+    let mut WCL = vec![];
+    let mut WCR = vec![];
+    let mut c = ScalarVector(vec![]);
+    let mut C = PointVector(vec![]);
+    let mut V = PointVector(vec![]);
+
+    for (block, statement) in statements.iter().enumerate() {
+      for (src, dst) in [(&template.WL, &mut WL), (&template.WR, &mut WR), (&template.WO, &mut WO)] {
+        let shifted = shift(src, block, n_block);
+        dst.highest_index = dst.highest_index.max(shifted.highest_index);
+        dst.data.extend(shifted.data);
+      }
+      let shifted_WV = shift(&template.WV, block, m_block);
+      WV.highest_index = WV.highest_index.max(shifted_WV.highest_index);
+      WV.data.extend(shifted_WV.data);
+
+      for (wcl_i, wcr_i) in template.WCL.iter().zip(template.WCR.iter()) {
+        WCL.push(shift(wcl_i, block, n_block));
+        WCR.push(shift(wcr_i, block, n_block));
+      }
+
+      c.0.extend(template.c.0.clone());
+      // Each copy's own commitment points, not the template's, so the statement this produces
+      // actually matches the per-copy witnesses `ArithmeticCircuitWitness::aggregate` concatenates
+      C.0.extend(statement.C.0.clone());
+      V.0.extend(statement.V.0.clone());
+    }
+
+    Self::new(generators, WL, WR, WO, WCL, WCR, WV, c, C, V)
+  }
+
   fn initial_transcript(&self, transcript: &mut T, AI: C::G, AO: C::G, S: C::G) -> YzChallenges<C> {
     transcript.domain_separate(b"arithmetic_circuit_proof");
 
@@ -292,12 +516,14 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
     ip_x
   }
 
-  pub fn prove<R: RngCore + CryptoRng>(
-    self,
+  // Everything shared by `prove`/`prove_norm`, up to (but not including) the final round's
+  // argument, which differs by backend.
+  fn prove_basis<R: RngCore + CryptoRng>(
+    &self,
     rng: &mut R,
     transcript: &mut T,
     mut witness: ArithmeticCircuitWitness<C>,
-  ) -> Result<ArithmeticCircuitProof<C>, AcError> {
+  ) -> Result<ProofBasis<C>, AcError> {
     let n = self.n();
     let c = self.c();
     let m = self.m();
@@ -471,7 +697,9 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
     // We now fill in the vector commitments
     // We use unused coefficients of l increasing from 0 (skipping ilr), and unused coefficients of
     // r decreasing from n' (skipping jlr)
-    for (i, ((c, WCL), WCR)) in witness.c.iter().zip(self.WCL).zip(self.WCR).enumerate() {
+    for (i, ((c, WCL), WCR)) in
+      witness.c.iter().zip(self.WCL.clone()).zip(self.WCR.clone()).enumerate()
+    {
       let i = i + 1;
       let j = ni - i;
 
@@ -514,6 +742,8 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
     }
 
     let x = Self::transcript_Ts(transcript, &T_before_ni, &T_after_ni);
+    // The wire/in-memory format stores these merged, with the split point implicit at `ni`
+    let T = T_before_ni.into_iter().chain(T_after_ni).collect();
 
     let poly_eval = |poly: &[ScalarVector<C::F>], x: &ScalarVector<_>| -> ScalarVector<_> {
       let mut res = ScalarVector::<C::F>::new(poly[0].0.len());
@@ -558,42 +788,142 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
       u
     };
 
-    // Use the Inner-Product argument to prove for this
-    let ip = {
-      // P = t_caret * g + l * g_bold + r * (y_inv * h_bold)
+    // l * g_bold + r * (y_inv * h_bold); the final round's own g term is left to the caller, as
+    // it differs by backend
+    let mut p_terms = Vec::with_capacity(1 + (2 * self.generators.len()));
+    assert_eq!(l.len(), r.len());
+    for (i, (l, r)) in l.0.iter().zip(r.0.iter()).enumerate() {
+      p_terms.push((*l, self.generators.g_bold(i)));
+      p_terms.push((y_inv[i] * r, self.generators.h_bold(i)));
+    }
 
-      let mut P_terms = Vec::with_capacity(1 + (2 * self.generators.len()));
-      assert_eq!(l.len(), r.len());
-      for (i, (l, r)) in l.0.iter().zip(r.0.iter()).enumerate() {
-        P_terms.push((*l, self.generators.g_bold(i)));
-        P_terms.push((y_inv[i] * r, self.generators.h_bold(i)));
-      }
+    // Protocol 1, inlined, since our final-round arguments are for Protocol 2
+    let challenge = Self::transcript_tau_x_u_t_caret(transcript, tau_x, u, t_caret);
 
-      // Protocol 1, inlined, since our IpStatement is for Protocol 2
-      let ip_x = Self::transcript_tau_x_u_t_caret(transcript, tau_x, u, t_caret);
-      P_terms.push((ip_x * t_caret, self.generators.g()));
-      IpStatement::new_without_P_transcript(
-        self.generators,
-        y_inv,
-        ip_x,
-        // Safe since IpStatement isn't a ZK proof
-        P::ProverWithoutTranscript(multiexp_vartime(&P_terms)),
-      )
-      .unwrap()
-      .prove(transcript, IpWitness::new(l, r).unwrap())
-      .unwrap()
-    };
+    Ok(ProofBasis { AI, AO, S, T, tau_x, u, t_caret, y_inv, l, r, p_terms, challenge })
+  }
 
-    Ok(ArithmeticCircuitProof { AI, AO, S, T_before_ni, T_after_ni, tau_x, u, t_caret, ip })
+  /// Prove for this arithmetic circuit statement, closing out the final round with Bulletproofs'
+  /// original (unweighted) inner-product argument.
+  pub fn prove<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    transcript: &mut T,
+    witness: ArithmeticCircuitWitness<C>,
+  ) -> Result<ArithmeticCircuitProof<C>, AcError> {
+    let basis = self.prove_basis(rng, transcript, witness)?;
+
+    // P = t_caret * g + l * g_bold + r * (y_inv * h_bold), weighted per Protocol 1
+    let mut p_terms = basis.p_terms;
+    p_terms.push((basis.challenge * basis.t_caret, self.generators.g()));
+    let p = multiexp_vartime(&p_terms);
+
+    let ip = IpStatement::new_without_P_transcript(
+      self.generators,
+      basis.y_inv,
+      basis.challenge,
+      // Safe since IpStatement isn't a ZK proof
+      P::ProverWithoutTranscript(p),
+    )
+    .unwrap()
+    .prove(transcript, IpWitness::new(basis.l, basis.r).unwrap())
+    .unwrap();
+
+    Ok(ArithmeticCircuitProof {
+      AI: basis.AI,
+      AO: basis.AO,
+      S: basis.S,
+      T: basis.T,
+      tau_x: basis.tau_x,
+      u: basis.u,
+      t_caret: basis.t_caret,
+      ip,
+    })
   }
 
-  pub fn verify<R: RngCore + CryptoRng>(
+  /// Prove for this arithmetic circuit statement, closing out the final round with the
+  /// Bulletproofs+-style weighted norm argument instead of the generic inner-product argument.
+  ///
+  /// Returns [`AcError::InconsistentWitness`] if `t_caret` doesn't actually decompose into the
+  /// weighted pairing `sum_i mu^i * n_i * l_i` of `r * y_inv` against `l`
+  /// ([`WipStatement::prove`](crate::inner_product::WipStatement::prove)'s `P`), which only holds
+  /// for specific circuits; see [`NormArithmeticCircuitProof`].
+  pub fn prove_norm<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    transcript: &mut T,
+    witness: ArithmeticCircuitWitness<C>,
+  ) -> Result<NormArithmeticCircuitProof<C>, AcError> {
+    let basis = self.prove_basis(rng, transcript, witness)?;
+    let mu = basis.challenge;
+
+    // h_bold's side of P is r * y_inv, so that's the vector this backend proves the norm of
+    let n = basis.r.clone() * &basis.y_inv;
+    // `WipStatement::prove` weights index `i` by `mu^i`, 0-indexed (its `n`/`l` scaling both start
+    // at the unweighted `weight = ONE` for index 0), so these powers must start at `mu^0`, not
+    // `mu^1` — an off-by-one here would make this guard demand `mu == 1` of an otherwise-honest
+    // witness, since `mu` is Fiat-Shamir-derived and so never actually 1
+    let mu_powers = ScalarVector::powers(mu, n.len()).0;
+    // This isn't actually a norm (n paired against itself): WipStatement::prove below pairs `n`
+    // against the independent `l` vector, so the quantity it (and therefore `t_caret`) commits to
+    // is the cross term `sum_i mu^i * n_i * l_i`, not `sum_i mu^i * n_i^2`
+    let expected_t_caret: C::F = n
+      .0
+      .iter()
+      .zip(basis.l.0.iter())
+      .zip(mu_powers.iter())
+      .map(|((n_i, l_i), mu_i)| *mu_i * *n_i * *l_i)
+      .sum();
+    if expected_t_caret != basis.t_caret {
+      Err(AcError::InconsistentWitness)?;
+    }
+
+    // P = t_caret * g + l * g_bold + n * h_bold; the norm argument's own per-round weighting
+    // supplies the `mu`-weighted sum of squares, so t_caret is added unscaled here
+    let mut p_terms = basis.p_terms;
+    p_terms.push((basis.t_caret, self.generators.g()));
+    let p = multiexp_vartime(&p_terms);
+
+    let h_tag = self.generators.h();
+    let norm = WipStatement::new_without_P_transcript(
+      self.generators,
+      mu,
+      h_tag,
+      // Safe since WipStatement isn't required to be a ZK proof here either
+      P::ProverWithoutTranscript(p),
+    )
+    // No further blinding is needed as `u` already accounts for every other term's mask
+    .prove(rng, transcript, WipWitness::new(n, basis.l, C::F::ZERO).unwrap())
+    .unwrap();
+
+    Ok(NormArithmeticCircuitProof {
+      AI: basis.AI,
+      AO: basis.AO,
+      S: basis.S,
+      T: basis.T,
+      tau_x: basis.tau_x,
+      u: basis.u,
+      t_caret: basis.t_caret,
+      norm,
+    })
+  }
+
+  // Everything shared by `verify`/`verify_norm`: pushing every term but the final round's
+  // argument into the batch verifier, and deriving the challenges that argument is checked under.
+  #[allow(clippy::too_many_arguments)]
+  fn verify_basis<R: RngCore + CryptoRng>(
     self,
     rng: &mut R,
     verifier: &mut BatchVerifier<C>,
     transcript: &mut T,
-    proof: ArithmeticCircuitProof<C>,
-  ) -> Result<(), AcError> {
+    AI: C::G,
+    AO: C::G,
+    S: C::G,
+    T: Vec<C::G>,
+    tau_x: C::F,
+    u: C::F,
+    t_caret: C::F,
+  ) -> Result<(ProofGenerators<'a, T, C>, ScalarVector<C::F>, C::F, C::F), AcError> {
     let n = self.n();
     let c = self.c();
     let m = self.m();
@@ -608,38 +938,38 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
     let l_r_poly_len = 1 + ni + 1;
     let t_poly_len = (2 * l_r_poly_len) - 1;
 
-    if proof.T_before_ni.len() != ni {
-      Err(AcError::IncorrectTBeforeNiLength)?;
-    }
-    if proof.T_after_ni.len() != (t_poly_len - ni - 1) {
-      Err(AcError::IncorrectTAfterNiLength)?;
+    // `T` merges `T_before_ni`/`T_after_ni`, split at the implicit `ni` pivot (the uncommitted
+    // coefficient opened directly via `t_caret`/`tau_x`)
+    if T.len() != (t_poly_len - 1) {
+      Err(AcError::IncorrectTLength)?;
     }
+    let (T_before_ni, T_after_ni) = T.split_at(ni);
+    let (T_before_ni, T_after_ni) = (T_before_ni.to_vec(), T_after_ni.to_vec());
 
-    let YzChallenges { y: _, y_inv, z } =
-      self.initial_transcript(transcript, proof.AI, proof.AO, proof.S);
+    let YzChallenges { y: _, y_inv, z } = self.initial_transcript(transcript, AI, AO, S);
 
     let delta = (self.WR.mul_vec(n, &z) * &y_inv).inner_product(&self.WL.mul_vec(n, &z));
 
-    let x = Self::transcript_Ts(transcript, &proof.T_before_ni, &proof.T_after_ni);
+    let x = Self::transcript_Ts(transcript, &T_before_ni, &T_after_ni);
 
     // Lines 88-90, modified per Generalized Bulletproofs as needed w.r.t. t
     {
       let verifier_weight = C::F::random(&mut *rng);
       // lhs of the equation, weighted to enable batch verification
-      verifier.g += proof.t_caret * verifier_weight;
-      verifier.h += proof.tau_x * verifier_weight;
+      verifier.g += t_caret * verifier_weight;
+      verifier.h += tau_x * verifier_weight;
 
       // rhs of the equation, negated to cause a sum to zero
       verifier.g -= verifier_weight * x[ni] * (delta + z.inner_product(&self.c));
       let V_weights = self.WV.mul_vec(m, &z) * x[ni];
       assert_eq!(V_weights.len(), self.V.len());
-      for pair in V_weights.0.into_iter().zip(self.V.0) {
+      for pair in V_weights.0.into_iter().zip(self.V.0.clone()) {
         verifier.additional.push((-verifier_weight * pair.0, pair.1));
       }
-      for (i, T) in proof.T_before_ni.into_iter().enumerate() {
+      for (i, T) in T_before_ni.clone().into_iter().enumerate() {
         verifier.additional.push((-verifier_weight * x[i], T));
       }
-      for (i, T) in proof.T_after_ni.into_iter().enumerate() {
+      for (i, T) in T_after_ni.clone().into_iter().enumerate() {
         verifier.additional.push((-verifier_weight * x[ni + 1 + i], T));
       }
     }
@@ -648,15 +978,15 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
 
     // This following block effectively calculates P, within the multiexp
     {
-      verifier.additional.push((verifier_weight * x[ilr], proof.AI));
-      verifier.additional.push((verifier_weight * x[io], proof.AO));
+      verifier.additional.push((verifier_weight * x[ilr], AI));
+      verifier.additional.push((verifier_weight * x[io], AO));
       // h' ** y is equivalent to h as h' is h ** y_inv
       let mut log2_n = 0;
       while (1 << log2_n) != n {
         log2_n += 1;
       }
       verifier.h_sum[log2_n] -= verifier_weight;
-      verifier.additional.push((verifier_weight * x[is], proof.S));
+      verifier.additional.push((verifier_weight * x[is], S));
 
       let mut h_bold_scalars = ScalarVector::new(n);
       // Lines 85-87 calculate WL, WR, WO
@@ -673,8 +1003,14 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
       // n'
       assert_eq!(self.C.len(), self.WCL.len());
       assert_eq!(self.C.len(), self.WCR.len());
-      for (i, ((C, WCL), WCR)) in
-        self.C.0.into_iter().zip(self.WCL.into_iter()).zip(self.WCR.into_iter()).enumerate()
+      for (i, ((C, WCL), WCR)) in self
+        .C
+        .0
+        .clone()
+        .into_iter()
+        .zip(self.WCL.clone())
+        .zip(self.WCR.clone())
+        .enumerate()
       {
         let i = i + 1;
         let j = ni - i;
@@ -692,22 +1028,67 @@ impl<'a, T: 'static + Transcript, C: Ciphersuite> ArithmeticCircuitStatement<'a,
       }
 
       // Remove u * h from P
-      verifier.h -= verifier_weight * proof.u;
+      verifier.h -= verifier_weight * u;
     }
 
-    // Prove for lines 88, 92 with an Inner-Product statement
-    // This inlines Protocol 1, as our IpStatement implements Protocol 2
-    let ip_x = Self::transcript_tau_x_u_t_caret(transcript, proof.tau_x, proof.u, proof.t_caret);
-    // P is amended with this additional term
-    verifier.g += verifier_weight * ip_x * proof.t_caret;
+    // Derive the challenge the final round's argument is evaluated/weighted under. The final
+    // round's own g term (which differs by backend) is left for the caller to add.
+    let challenge = Self::transcript_tau_x_u_t_caret(transcript, tau_x, u, t_caret);
+
+    Ok((self.generators, y_inv, verifier_weight, challenge))
+  }
+
+  /// Verify this arithmetic circuit statement was proven for with [`Self::prove`], pushing its
+  /// terms into the shared [`BatchVerifier`].
+  pub fn verify<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    verifier: &mut BatchVerifier<C>,
+    transcript: &mut T,
+    proof: ArithmeticCircuitProof<C>,
+  ) -> Result<(), AcError> {
+    let ArithmeticCircuitProof { AI, AO, S, T, tau_x, u, t_caret, ip } = proof;
+    let (generators, y_inv, verifier_weight, ip_x) =
+      self.verify_basis(rng, verifier, transcript, AI, AO, S, T, tau_x, u, t_caret)?;
+    verifier.g += verifier_weight * ip_x * t_caret;
+
     IpStatement::new_without_P_transcript(
-      self.generators,
+      generators,
       y_inv,
       ip_x,
       P::VerifierWithoutTranscript { verifier_weight },
     )
     .unwrap()
-    .verify(rng, verifier, transcript, proof.ip)
+    .verify(rng, verifier, transcript, ip)
+    .map_err(AcError::Ip)?;
+
+    Ok(())
+  }
+
+  /// Verify this arithmetic circuit statement was proven for with [`Self::prove_norm`], pushing
+  /// its terms into the shared [`BatchVerifier`].
+  pub fn verify_norm<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    verifier: &mut BatchVerifier<C>,
+    transcript: &mut T,
+    proof: NormArithmeticCircuitProof<C>,
+  ) -> Result<(), AcError> {
+    let NormArithmeticCircuitProof { AI, AO, S, T, tau_x, u, t_caret, norm } = proof;
+    let (generators, _y_inv, verifier_weight, mu) =
+      self.verify_basis(rng, verifier, transcript, AI, AO, S, T, tau_x, u, t_caret)?;
+    // The norm argument's own per-round weighting supplies the weighted sum of squares, so
+    // t_caret is added unscaled here, unlike the Ip backend's `ip_x * t_caret`
+    verifier.g += verifier_weight * t_caret;
+
+    let h_tag = generators.h();
+    WipStatement::new_without_P_transcript(
+      generators,
+      mu,
+      h_tag,
+      P::VerifierWithoutTranscript { verifier_weight },
+    )
+    .verify(rng, verifier, transcript, norm)
     .map_err(AcError::Ip)?;
 
     Ok(())
@@ -0,0 +1,200 @@
+use rand_core::{RngCore, CryptoRng};
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use transcript::Transcript;
+
+use multiexp::multiexp;
+use ciphersuite::{
+  group::{ff::Field, GroupEncoding},
+  Ciphersuite,
+};
+
+use crate::{
+  ScalarVector, PointVector, ProofGenerators, PedersenCommitment, PedersenVectorCommitment,
+  arithmetic_circuit_proof::{ArithmeticCircuitWitness, AcError},
+};
+
+/// An error incurred while folding/accumulating arithmetic circuit instances.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FoldError {
+  DifferingWitnessLengths,
+  DifferingAmountsOfCommitments,
+  Ac(AcError),
+  // Proving/verifying a relaxed instance which has actually been folded (u != 1 or a non-zero
+  // error vector) requires its own folded-circuit argument, which isn't implemented yet; only
+  // the degenerate, unfolded accumulator (fresh off `RelaxedWitness::new`) can be proven/verified
+  // via the existing, non-relaxed argument.
+  RelaxedProvingNotYetSupported,
+}
+
+/// A relaxed instance, per the Nova/HyperNova folding scheme: the committed terms of an
+/// [`ArithmeticCircuitStatement`](crate::arithmetic_circuit_proof::ArithmeticCircuitStatement)
+/// instance, plus the slack `u` and a commitment to the error vector `E` which make the relation
+/// `aL ∘ aR = u · aO + E` satisfiable by a linear combination of two satisfying witnesses.
+#[derive(Clone, Zeroize)]
+pub struct RelaxedInstance<C: Ciphersuite> {
+  pub C: PointVector<C>,
+  pub V: PointVector<C>,
+  pub E_comm: C::G,
+  pub u: C::F,
+}
+
+/// The witness opening a [`RelaxedInstance`]: a relaxed arithmetic circuit witness carrying the
+/// slack `u` and error vector `E`.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct RelaxedWitness<C: Ciphersuite> {
+  pub aL: ScalarVector<C::F>,
+  pub aR: ScalarVector<C::F>,
+  pub aO: ScalarVector<C::F>,
+  pub c: Vec<PedersenVectorCommitment<C>>,
+  pub v: Vec<PedersenCommitment<C>>,
+  pub E: ScalarVector<C::F>,
+  pub E_mask: C::F,
+  pub u: C::F,
+}
+
+impl<C: Ciphersuite> RelaxedWitness<C> {
+  /// Lift a standard, non-relaxed witness (one which exactly satisfies `aL ∘ aR = aO`) into the
+  /// degenerate relaxed witness `u = 1, E = 0` that starts an accumulator.
+  pub fn new(witness: ArithmeticCircuitWitness<C>) -> Self {
+    let ArithmeticCircuitWitness { aL, aR, aO, c, v } = witness;
+    let E = ScalarVector::new(aL.len());
+    RelaxedWitness { aL, aR, aO, c, v, E, E_mask: C::F::ZERO, u: C::F::ONE }
+  }
+
+  /// The matching degenerate [`RelaxedInstance`] for a witness produced by [`Self::new`], given
+  /// the generators the underlying circuit's commitments were opened against.
+  pub fn instance<'a, T: 'static + Transcript>(
+    &self,
+    generators: &ProofGenerators<'a, T, C>,
+  ) -> RelaxedInstance<C> {
+    let C = PointVector(
+      self
+        .c
+        .iter()
+        .map(|c| c.commit(generators.g_bold_slice(), generators.h_bold_slice(), generators.h()))
+        .collect(),
+    );
+    let V = PointVector(self.v.iter().map(|v| v.commit(generators.g(), generators.h())).collect());
+    let E_comm = multiexp(&[(self.E_mask, generators.h())]);
+    RelaxedInstance { C, V, E_comm, u: self.u }
+  }
+}
+
+// The cross term for the multiplication-gate relaxation, `T = (aL_1 ∘ aR_2 + aL_2 ∘ aR_1) -
+// (u_1 * aO_2 + u_2 * aO_1)`, committed to with a fresh blind so the verifier can fold the
+// instances without learning either witness.
+//
+// `T` is exactly what makes the folded relation `aL' ∘ aR' = u' * aO' + E'` hold: expanding
+// `aL' ∘ aR'` for `aL' = aL_1 + r*aL_2` (and `aR'` likewise) produces a degree-1-in-`r` term of
+// `aL_1 ∘ aR_2 + aL_2 ∘ aR_1`, while the corresponding term of `u' * aO'` is `u_1*aO_2 + u_2*aO_1`;
+// without subtracting the latter here, `E' = E_1 + r*T + r^2*E_2` would absorb the mismatch and
+// the folded witness/instance pair would no longer satisfy the relaxed relation it claims to.
+fn cross_term<C: Ciphersuite>(
+  acc: &RelaxedWitness<C>,
+  instance: &RelaxedWitness<C>,
+) -> ScalarVector<C::F> {
+  ((acc.aL.clone() * &instance.aR) + &(instance.aL.clone() * &acc.aR)) -
+    &((instance.aO.clone() * acc.u) + &(acc.aO.clone() * instance.u))
+}
+
+/// Fold a fresh, unrelaxed instance into a running [`RelaxedInstance`]/[`RelaxedWitness`]
+/// accumulator pair under a transcript-derived challenge `r`, per Nova/HyperNova.
+///
+/// `instance` must share the same constraint matrices (and therefore witness/commitment shape)
+/// as every instance already folded into `acc`; this isn't (and can't be) checked here, as this
+/// module has no access to the shared circuit definition.
+///
+/// Returns the updated accumulator and the cross-term commitment `T`, which the verifier needs
+/// (alongside the fresh instance's own commitments) to fold its copy of the accumulator the same
+/// way.
+pub fn fold<R: RngCore + CryptoRng, T: 'static + Transcript, C: Ciphersuite>(
+  rng: &mut R,
+  transcript: &mut T,
+  generators: &ProofGenerators<'_, T, C>,
+  acc: (RelaxedInstance<C>, RelaxedWitness<C>),
+  instance: (RelaxedInstance<C>, RelaxedWitness<C>),
+) -> Result<((RelaxedInstance<C>, RelaxedWitness<C>), C::G), FoldError> {
+  let (acc_instance, acc_witness) = acc;
+  let (instance_instance, instance_witness) = instance;
+
+  if acc_witness.aL.len() != instance_witness.aL.len() {
+    Err(FoldError::DifferingWitnessLengths)?;
+  }
+  if (acc_instance.C.len() != instance_instance.C.len()) ||
+    (acc_instance.V.len() != instance_instance.V.len())
+  {
+    Err(FoldError::DifferingAmountsOfCommitments)?;
+  }
+
+  let T_vec = cross_term(&acc_witness, &instance_witness);
+  let T_mask = C::F::random(&mut *rng);
+  let T = multiexp(
+    &T_vec
+      .0
+      .iter()
+      .enumerate()
+      .map(|(i, T_i)| (*T_i, generators.g_bold(i)))
+      .chain(core::iter::once((T_mask, generators.h())))
+      .collect::<Vec<_>>(),
+  );
+
+  transcript.domain_separate(b"arithmetic_circuit_fold");
+  transcript.append_message(b"T", T.to_bytes());
+  let r = C::hash_to_F(b"arithmetic_circuit_fold", transcript.challenge(b"r").as_ref());
+
+  let folded_witness = RelaxedWitness {
+    aL: acc_witness.aL.clone() + &(instance_witness.aL.clone() * r),
+    aR: acc_witness.aR.clone() + &(instance_witness.aR.clone() * r),
+    aO: acc_witness.aO.clone() + &(instance_witness.aO.clone() * r),
+    c: acc_witness
+      .c
+      .iter()
+      .zip(instance_witness.c.iter())
+      .map(|(acc_c, instance_c)| PedersenVectorCommitment {
+        g_values: acc_c.g_values.clone() + &(instance_c.g_values.clone() * r),
+        h_values: acc_c.h_values.clone() + &(instance_c.h_values.clone() * r),
+        mask: acc_c.mask + (r * instance_c.mask),
+      })
+      .collect(),
+    v: acc_witness
+      .v
+      .iter()
+      .zip(instance_witness.v.iter())
+      .map(|(acc_v, instance_v)| PedersenCommitment {
+        value: acc_v.value + (r * instance_v.value),
+        mask: acc_v.mask + (r * instance_v.mask),
+      })
+      .collect(),
+    // E' = E_1 + r * T + r^2 * E_2, where E_2 = 0 as `instance` is an unrelaxed, fresh witness
+    E: acc_witness.E.clone() + &(T_vec * r),
+    E_mask: acc_witness.E_mask + (r * T_mask),
+    u: acc_witness.u + (r * instance_witness.u),
+  };
+
+  let folded_instance = RelaxedInstance {
+    C: PointVector(
+      acc_instance
+        .C
+        .0
+        .iter()
+        .zip(instance_instance.C.0.iter())
+        .map(|(acc_c, instance_c)| *acc_c + (*instance_c * r))
+        .collect(),
+    ),
+    V: PointVector(
+      acc_instance
+        .V
+        .0
+        .iter()
+        .zip(instance_instance.V.0.iter())
+        .map(|(acc_v, instance_v)| *acc_v + (*instance_v * r))
+        .collect(),
+    ),
+    E_comm: acc_instance.E_comm + (T * r),
+    u: acc_instance.u + (r * instance_instance.u),
+  };
+
+  Ok(((folded_instance, folded_witness), T))
+}
@@ -0,0 +1,549 @@
+use rand_core::{RngCore, CryptoRng};
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use transcript::Transcript;
+
+use multiexp::multiexp;
+use ciphersuite::{
+  group::{
+    ff::{Field, PrimeField},
+    GroupEncoding,
+  },
+  Ciphersuite,
+};
+
+use crate::{ScalarVector, ProofGenerators, BatchVerifier};
+
+/// An error incurred during inner-product argument operations.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IpError {
+  DifferingLrLengths,
+  InconsistentWitness,
+}
+
+/// The point an inner-product statement proves an opening of.
+///
+/// If the prover already has `P`, it's passed directly (optionally transcripting it, if the
+/// caller hasn't already bound it to the transcript themselves). If the verifier is checking this
+/// as part of a larger statement which has already folded `P`'s terms into its own
+/// [`BatchVerifier`], only the weight used to do so is needed.
+pub enum P<C: Ciphersuite> {
+  Prover(C::G),
+  ProverWithoutTranscript(C::G),
+  VerifierWithoutTranscript { verifier_weight: C::F },
+}
+
+/// The witness for an inner-product statement.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct IpWitness<C: Ciphersuite> {
+  a: ScalarVector<C::F>,
+  b: ScalarVector<C::F>,
+}
+
+impl<C: Ciphersuite> IpWitness<C> {
+  /// Construct a new witness for an inner-product statement.
+  pub fn new(a: ScalarVector<C::F>, b: ScalarVector<C::F>) -> Result<Self, IpError> {
+    if a.len() != b.len() {
+      Err(IpError::DifferingLrLengths)?;
+    }
+    Ok(IpWitness { a, b })
+  }
+}
+
+/// A proof for an inner-product statement, logarithmic in the amount of generators.
+#[derive(Clone, Debug, Zeroize)]
+pub struct IpProof<C: Ciphersuite> {
+  L: Vec<C::G>,
+  R: Vec<C::G>,
+  a: C::F,
+  b: C::F,
+}
+
+impl<C: Ciphersuite> IpProof<C> {
+  /// Write this proof in a canonical format.
+  pub fn write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    for L in &self.L {
+      writer.write_all(L.to_bytes().as_ref())?;
+    }
+    for R in &self.R {
+      writer.write_all(R.to_bytes().as_ref())?;
+    }
+    writer.write_all(self.a.to_repr().as_ref())?;
+    writer.write_all(self.b.to_repr().as_ref())?;
+    Ok(())
+  }
+
+  /// Read a proof with `log2_n` rounds (the base-2 log of the statement's amount of generators)
+  /// from a canonical format, rejecting non-canonical point/scalar encodings.
+  pub fn read<R: std::io::Read>(reader: &mut R, log2_n: usize) -> std::io::Result<Self> {
+    let invalid_data = || std::io::Error::new(std::io::ErrorKind::InvalidData, "non-canonical encoding");
+
+    let read_point = |reader: &mut R| -> std::io::Result<C::G> {
+      let mut repr = <C::G as GroupEncoding>::Repr::default();
+      reader.read_exact(repr.as_mut())?;
+      Option::from(C::G::from_bytes(&repr)).ok_or_else(invalid_data)
+    };
+    let read_scalar = |reader: &mut R| -> std::io::Result<C::F> {
+      let mut repr = <C::F as PrimeField>::Repr::default();
+      reader.read_exact(repr.as_mut())?;
+      Option::from(C::F::from_repr(repr)).ok_or_else(invalid_data)
+    };
+
+    let mut L = Vec::with_capacity(log2_n);
+    for _ in 0 .. log2_n {
+      L.push(read_point(reader)?);
+    }
+    let mut R_vec = Vec::with_capacity(log2_n);
+    for _ in 0 .. log2_n {
+      R_vec.push(read_point(reader)?);
+    }
+    let a = read_scalar(reader)?;
+    let b = read_scalar(reader)?;
+    Ok(IpProof { L, R: R_vec, a, b })
+  }
+}
+
+/// Bulletproofs' original (unweighted) inner-product argument, Protocol 2.
+///
+/// This proves knowledge of `a`, `b` such that
+/// `P = <a, g_bold> + <b, h_bold * h_bold_weights> + x * <a, b> * g`.
+#[derive(Clone)]
+pub struct IpStatement<'a, T: 'static + Transcript, C: Ciphersuite> {
+  generators: ProofGenerators<'a, T, C>,
+  h_bold_weights: ScalarVector<C::F>,
+  x: C::F,
+  p: P<C>,
+}
+
+impl<'a, T: 'static + Transcript, C: Ciphersuite> IpStatement<'a, T, C> {
+  fn new_internal(
+    generators: ProofGenerators<'a, T, C>,
+    h_bold_weights: ScalarVector<C::F>,
+    x: C::F,
+    p: P<C>,
+  ) -> Result<Self, IpError> {
+    if h_bold_weights.len() != generators.len() {
+      Err(IpError::InconsistentWitness)?;
+    }
+    Ok(IpStatement { generators, h_bold_weights, x, p })
+  }
+
+  /// Create a new inner-product statement, transcripting `P`.
+  pub fn new(
+    generators: ProofGenerators<'a, T, C>,
+    h_bold_weights: ScalarVector<C::F>,
+    x: C::F,
+    p: C::G,
+  ) -> Result<Self, IpError> {
+    Self::new_internal(generators, h_bold_weights, x, P::Prover(p))
+  }
+
+  /// Create a new inner-product statement without transcripting `P`.
+  ///
+  /// This is for use by statements (such as an arithmetic circuit proof) which have already bound
+  /// `P`'s terms to the transcript themselves.
+  pub fn new_without_P_transcript(
+    generators: ProofGenerators<'a, T, C>,
+    h_bold_weights: ScalarVector<C::F>,
+    x: C::F,
+    p: P<C>,
+  ) -> Result<Self, IpError> {
+    Self::new_internal(generators, h_bold_weights, x, p)
+  }
+
+  fn challenge(transcript: &mut T, L: C::G, R: C::G) -> (C::F, C::F) {
+    transcript.append_message(b"L", L.to_bytes());
+    transcript.append_message(b"R", R.to_bytes());
+    let e = C::hash_to_F(b"inner_product", transcript.challenge(b"e").as_ref());
+    let e_inv = e.invert().unwrap();
+    (e, e_inv)
+  }
+
+  /// Prove for this inner-product statement.
+  pub fn prove(self, transcript: &mut T, witness: IpWitness<C>) -> Result<IpProof<C>, IpError> {
+    let IpStatement { generators, h_bold_weights, x, p } = self;
+    let mut n = generators.len();
+    if (witness.a.len() != n) || (witness.b.len() != n) {
+      Err(IpError::InconsistentWitness)?;
+    }
+
+    if let P::Prover(point) = &p {
+      transcript.append_message(b"P", point.to_bytes());
+    }
+
+    let mut g_bold = (0 .. n).map(|i| generators.g_bold(i)).collect::<Vec<_>>();
+    // Fold the per-index weight into h_bold once, reducing the rest of this function to the
+    // textbook unweighted argument
+    let mut h_bold =
+      (0 .. n).map(|i| generators.h_bold(i) * h_bold_weights[i]).collect::<Vec<_>>();
+    let g = generators.g();
+
+    let mut a = witness.a.0;
+    let mut b = witness.b.0;
+
+    let mut L_vec = Vec::with_capacity(usize::BITS as usize);
+    let mut R_vec = Vec::with_capacity(usize::BITS as usize);
+
+    while n > 1 {
+      n /= 2;
+
+      let (a1, a2) = a.split_at(n);
+      let (b1, b2) = b.split_at(n);
+      let (g_bold1, g_bold2) = g_bold.split_at(n);
+      let (h_bold1, h_bold2) = h_bold.split_at(n);
+
+      let c_l = ScalarVector(a1.to_vec()).inner_product(&ScalarVector(b2.to_vec()));
+      let c_r = ScalarVector(a2.to_vec()).inner_product(&ScalarVector(b1.to_vec()));
+
+      let mut L_terms = Vec::with_capacity((2 * n) + 1);
+      for i in 0 .. n {
+        L_terms.push((a1[i], g_bold2[i]));
+        L_terms.push((b2[i], h_bold1[i]));
+      }
+      L_terms.push((x * c_l, g));
+      let L = multiexp(&L_terms);
+
+      let mut R_terms = Vec::with_capacity((2 * n) + 1);
+      for i in 0 .. n {
+        R_terms.push((a2[i], g_bold1[i]));
+        R_terms.push((b1[i], h_bold2[i]));
+      }
+      R_terms.push((x * c_r, g));
+      let R = multiexp(&R_terms);
+
+      let (e, e_inv) = Self::challenge(transcript, L, R);
+
+      a = (0 .. n).map(|i| (a1[i] * e) + (a2[i] * e_inv)).collect();
+      b = (0 .. n).map(|i| (b1[i] * e_inv) + (b2[i] * e)).collect();
+      g_bold = (0 .. n).map(|i| (g_bold1[i] * e_inv) + (g_bold2[i] * e)).collect();
+      h_bold = (0 .. n).map(|i| (h_bold1[i] * e) + (h_bold2[i] * e_inv)).collect();
+
+      L_vec.push(L);
+      R_vec.push(R);
+    }
+
+    Ok(IpProof { L: L_vec, R: R_vec, a: a[0], b: b[0] })
+  }
+
+  /// Verify this inner-product statement, pushing its terms into the shared [`BatchVerifier`].
+  pub fn verify<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    verifier: &mut BatchVerifier<C>,
+    transcript: &mut T,
+    proof: IpProof<C>,
+  ) -> Result<(), IpError> {
+    let IpStatement { generators, h_bold_weights, x, p } = self;
+    let n = generators.len();
+
+    let mut log2_n = 0;
+    while (1 << log2_n) != n {
+      log2_n += 1;
+    }
+    if (proof.L.len() != log2_n) || (proof.R.len() != log2_n) {
+      Err(IpError::DifferingLrLengths)?;
+    }
+
+    let verifier_weight = match p {
+      P::Prover(point) => {
+        transcript.append_message(b"P", point.to_bytes());
+        let weight = C::F::random(&mut *rng);
+        verifier.additional.push((weight, point));
+        weight
+      }
+      P::ProverWithoutTranscript(point) => {
+        let weight = C::F::random(&mut *rng);
+        verifier.additional.push((weight, point));
+        weight
+      }
+      P::VerifierWithoutTranscript { verifier_weight } => verifier_weight,
+    };
+
+    let mut challenges = Vec::with_capacity(log2_n);
+    let mut challenges_inv = Vec::with_capacity(log2_n);
+    for (L, R) in proof.L.iter().zip(proof.R.iter()) {
+      let (e, e_inv) = Self::challenge(transcript, *L, *R);
+      challenges.push(e);
+      challenges_inv.push(e_inv);
+    }
+
+    // Recompute, per generator, the product of challenges (or their inverse) an honest prover's
+    // folds would have applied to it, in O(n) via the doubling recurrence: `g_bold_scalars[0]` is
+    // the product of every round's inverse challenge (index 0's bits are all unset), and flipping
+    // the lowest set bit of any other index `i` from 0 to 1 replaces that round's inverse
+    // challenge factor with the challenge itself, i.e. multiplies by `challenge^2`.
+    let mut g_bold_scalars = vec![C::F::ONE; n];
+    g_bold_scalars[0] = challenges_inv.iter().fold(C::F::ONE, |acc, e_inv| acc * e_inv);
+    for i in 1 .. n {
+      let bit = i.trailing_zeros() as usize;
+      let round = log2_n - 1 - bit;
+      g_bold_scalars[i] = g_bold_scalars[i - (1 << bit)] * challenges[round].square();
+    }
+    let mut h_bold_scalars = vec![C::F::ONE; n];
+    for i in 0 .. n {
+      // h_bold folds with the inverse weighting, relative to g_bold, at every round
+      h_bold_scalars[i] = g_bold_scalars[n - 1 - i];
+    }
+
+    for (e, e_inv) in challenges.iter().zip(challenges_inv.iter()) {
+      verifier.additional.push((verifier_weight * e.square(), proof.L.remove(0)));
+      verifier.additional.push((verifier_weight * e_inv.square(), proof.R.remove(0)));
+    }
+
+    for i in 0 .. n {
+      verifier.g_bold[i] -= verifier_weight * proof.a * g_bold_scalars[i];
+      verifier.h_bold[i] -= verifier_weight * proof.b * h_bold_scalars[i] * h_bold_weights[i];
+    }
+    verifier.g -= verifier_weight * x * proof.a * proof.b;
+
+    Ok(())
+  }
+}
+
+/// The witness for the weighted inner-product argument (Bulletproofs+ style): the norm vector
+/// `n`, the linear vector `l`, and the blinding scalar `alpha`.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct WipWitness<C: Ciphersuite> {
+  n: ScalarVector<C::F>,
+  l: ScalarVector<C::F>,
+  alpha: C::F,
+}
+
+impl<C: Ciphersuite> WipWitness<C> {
+  /// Construct a new witness for a weighted inner-product statement.
+  pub fn new(n: ScalarVector<C::F>, l: ScalarVector<C::F>, alpha: C::F) -> Result<Self, IpError> {
+    if n.len() != l.len() {
+      Err(IpError::DifferingLrLengths)?;
+    }
+    Ok(WipWitness { n, l, alpha })
+  }
+}
+
+/// A proof for a weighted inner-product statement, logarithmic in the amount of generators, with
+/// a single folded blinding opening rather than one per round.
+#[derive(Clone, Debug, Zeroize)]
+pub struct WipProof<C: Ciphersuite> {
+  L: Vec<C::G>,
+  R: Vec<C::G>,
+  n: C::F,
+  l: C::F,
+  r: C::F,
+}
+
+/// The Bulletproofs+ weighted inner-product argument.
+///
+/// This proves knowledge of `n`, `l`, `alpha` such that
+/// `P = <l, g_bold> + <n, h_bold> + (sum_i y^i * n_i * l_i) * g + alpha * h_tag`,
+/// folding both vectors, and the blinding they carry, under a single challenge per round.
+#[derive(Clone)]
+pub struct WipStatement<'a, T: 'static + Transcript, C: Ciphersuite> {
+  generators: ProofGenerators<'a, T, C>,
+  y: C::F,
+  h_tag: C::G,
+  p: P<C>,
+}
+
+impl<'a, T: 'static + Transcript, C: Ciphersuite> WipStatement<'a, T, C> {
+  /// Create a new weighted inner-product statement, transcripting `P`.
+  pub fn new(generators: ProofGenerators<'a, T, C>, y: C::F, h_tag: C::G, p: C::G) -> Self {
+    WipStatement { generators, y, h_tag, p: P::Prover(p) }
+  }
+
+  /// Create a new weighted inner-product statement without transcripting `P`, mirroring
+  /// [`IpStatement::new_without_P_transcript`].
+  pub fn new_without_P_transcript(
+    generators: ProofGenerators<'a, T, C>,
+    y: C::F,
+    h_tag: C::G,
+    p: P<C>,
+  ) -> Self {
+    WipStatement { generators, y, h_tag, p }
+  }
+
+  fn challenge(transcript: &mut T, L: C::G, R: C::G) -> (C::F, C::F) {
+    transcript.append_message(b"L", L.to_bytes());
+    transcript.append_message(b"R", R.to_bytes());
+    let e = C::hash_to_F(b"weighted_inner_product", transcript.challenge(b"e").as_ref());
+    let e_inv = e.invert().unwrap();
+    (e, e_inv)
+  }
+
+  /// Prove for this weighted inner-product statement.
+  ///
+  /// `n` and `l` are pre-scaled by `y^i`/`y^-i` respectively (folded into `n` and `h_bold`, once,
+  /// up front) so `<n, l>` under the fold below equals the weighted inner product `sum_i
+  /// y^i * n_i * l_i` this statement actually proves, reducing the rest of this function to the
+  /// same symmetric `a`/`b` fold [`IpStatement::prove`] uses (`l` plays `a`, pre-scaled `n` plays
+  /// `b`), plus a running fold of the blinding `alpha` alongside it.
+  pub fn prove<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    transcript: &mut T,
+    witness: WipWitness<C>,
+  ) -> Result<WipProof<C>, IpError> {
+    let WipStatement { generators, y, h_tag, p } = self;
+    let mut size = generators.len();
+    if (witness.n.len() != size) || (witness.l.len() != size) {
+      Err(IpError::InconsistentWitness)?;
+    }
+
+    if let P::Prover(point) = &p {
+      transcript.append_message(b"P", point.to_bytes());
+    }
+
+    let mut g_bold = (0 .. size).map(|i| generators.g_bold(i)).collect::<Vec<_>>();
+    let y_inv = y.invert().unwrap();
+    let mut h_bold = {
+      let mut weight = C::F::ONE;
+      (0 .. size)
+        .map(|i| {
+          let h = generators.h_bold(i) * weight;
+          weight *= y_inv;
+          h
+        })
+        .collect::<Vec<_>>()
+    };
+    let g = generators.g();
+
+    let mut l_vec = witness.l.0;
+    let mut n_vec = {
+      let mut weight = C::F::ONE;
+      witness
+        .n
+        .0
+        .iter()
+        .map(|n| {
+          let scaled = *n * weight;
+          weight *= y;
+          scaled
+        })
+        .collect::<Vec<_>>()
+    };
+    let mut alpha = witness.alpha;
+
+    let mut L_vec = Vec::with_capacity(usize::BITS as usize);
+    let mut R_vec = Vec::with_capacity(usize::BITS as usize);
+
+    while size > 1 {
+      size /= 2;
+
+      let (l1, l2) = l_vec.split_at(size);
+      let (n1, n2) = n_vec.split_at(size);
+      let (g1, g2) = g_bold.split_at(size);
+      let (h1, h2) = h_bold.split_at(size);
+
+      let alpha_l = C::F::random(&mut *rng);
+      let alpha_r = C::F::random(&mut *rng);
+
+      let c_l = ScalarVector(l1.to_vec()).inner_product(&ScalarVector(n2.to_vec()));
+      let c_r = ScalarVector(l2.to_vec()).inner_product(&ScalarVector(n1.to_vec()));
+
+      let mut L_terms = Vec::with_capacity((2 * size) + 2);
+      for i in 0 .. size {
+        L_terms.push((l1[i], g2[i]));
+        L_terms.push((n2[i], h1[i]));
+      }
+      L_terms.push((c_l, g));
+      L_terms.push((alpha_l, h_tag));
+      let L = multiexp(&L_terms);
+
+      let mut R_terms = Vec::with_capacity((2 * size) + 2);
+      for i in 0 .. size {
+        R_terms.push((l2[i], g1[i]));
+        R_terms.push((n1[i], h2[i]));
+      }
+      R_terms.push((c_r, g));
+      R_terms.push((alpha_r, h_tag));
+      let R = multiexp(&R_terms);
+
+      let (e, e_inv) = Self::challenge(transcript, L, R);
+
+      l_vec = (0 .. size).map(|i| (l1[i] * e) + (l2[i] * e_inv)).collect();
+      n_vec = (0 .. size).map(|i| (n1[i] * e_inv) + (n2[i] * e)).collect();
+      g_bold = (0 .. size).map(|i| (g1[i] * e_inv) + (g2[i] * e)).collect();
+      h_bold = (0 .. size).map(|i| (h1[i] * e) + (h2[i] * e_inv)).collect();
+
+      alpha += (alpha_l * e.square()) + (alpha_r * e_inv.square());
+
+      L_vec.push(L);
+      R_vec.push(R);
+    }
+
+    Ok(WipProof { L: L_vec, R: R_vec, n: n_vec[0], l: l_vec[0], r: alpha })
+  }
+
+  /// Verify this weighted inner-product statement, pushing its terms into the shared
+  /// [`BatchVerifier`].
+  pub fn verify<R: RngCore + CryptoRng>(
+    self,
+    rng: &mut R,
+    verifier: &mut BatchVerifier<C>,
+    transcript: &mut T,
+    mut proof: WipProof<C>,
+  ) -> Result<(), IpError> {
+    let WipStatement { generators, y, h_tag, p } = self;
+    let size = generators.len();
+
+    let mut log2_n = 0;
+    while (1 << log2_n) != size {
+      log2_n += 1;
+    }
+    if (proof.L.len() != log2_n) || (proof.R.len() != log2_n) {
+      Err(IpError::DifferingLrLengths)?;
+    }
+
+    let verifier_weight = match p {
+      P::Prover(point) => {
+        transcript.append_message(b"P", point.to_bytes());
+        let weight = C::F::random(&mut *rng);
+        verifier.additional.push((weight, point));
+        weight
+      }
+      P::ProverWithoutTranscript(point) => {
+        let weight = C::F::random(&mut *rng);
+        verifier.additional.push((weight, point));
+        weight
+      }
+      P::VerifierWithoutTranscript { verifier_weight } => verifier_weight,
+    };
+
+    let mut challenges = Vec::with_capacity(log2_n);
+    let mut challenges_inv = Vec::with_capacity(log2_n);
+    for (L, R) in proof.L.iter().zip(proof.R.iter()) {
+      let (e, e_inv) = Self::challenge(transcript, *L, *R);
+      challenges.push(e);
+      challenges_inv.push(e_inv);
+    }
+
+    // `l` folds identically to `IpStatement`'s `a` (paired with `g_bold`); `n` (pre-scaled by
+    // `y^i`, unwound below via `y^-i` on the generator side) folds identically to its `b` (paired
+    // with `h_bold`), so the same O(n) doubling recurrence reconstructs both generators' per-index
+    // fold scalars
+    let mut g_bold_scalars = vec![C::F::ONE; size];
+    g_bold_scalars[0] = challenges_inv.iter().fold(C::F::ONE, |acc, e_inv| acc * e_inv);
+    for i in 1 .. size {
+      let bit = i.trailing_zeros() as usize;
+      let round = log2_n - 1 - bit;
+      g_bold_scalars[i] = g_bold_scalars[i - (1 << bit)] * challenges[round].square();
+    }
+    let h_bold_scalars: Vec<C::F> = (0 .. size).map(|i| g_bold_scalars[size - 1 - i]).collect();
+
+    for (e, e_inv) in challenges.iter().zip(challenges_inv.iter()) {
+      verifier.additional.push((verifier_weight * e.square(), proof.L.remove(0)));
+      verifier.additional.push((verifier_weight * e_inv.square(), proof.R.remove(0)));
+    }
+
+    let y_inv = y.invert().unwrap();
+    let mut h_bold_weight = C::F::ONE;
+    for i in 0 .. size {
+      verifier.g_bold[i] -= verifier_weight * proof.l * g_bold_scalars[i];
+      verifier.h_bold[i] -= verifier_weight * proof.n * h_bold_scalars[i] * h_bold_weight;
+      h_bold_weight *= y_inv;
+    }
+    verifier.g -= verifier_weight * proof.n * proof.l;
+    verifier.additional.push((-verifier_weight * proof.r, h_tag));
+
+    Ok(())
+  }
+}
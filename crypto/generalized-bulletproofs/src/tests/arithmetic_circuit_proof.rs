@@ -0,0 +1,64 @@
+use rand_core::OsRng;
+
+use transcript::{Transcript, RecommendedTranscript};
+
+use ciphersuite::{group::ff::Field, Ciphersuite, Ristretto};
+
+use crate::{
+  ScalarVector, ScalarMatrix, PointVector, ProofGenerators,
+  arithmetic_circuit_proof::{ArithmeticCircuitStatement, ArithmeticCircuitWitness},
+  tests::generators,
+};
+
+// A statement with a single multiplication gate (aL * aR = aO) and no linear constraints, vector
+// commitments, or Pedersen commitments: the simplest circuit for which `prove_norm`'s
+// decomposition of `t_caret` as `sum_i mu^i * n_i * l_i` holds unconditionally, since with one
+// multiplication and no constraints `l`/`r` each reduce to a single term, and the 0th power of
+// any challenge (`y`, `mu`) is 1 regardless of its (Fiat-Shamir-derived) value.
+fn single_gate_statement(
+  generators: ProofGenerators<'_, RecommendedTranscript, Ristretto>,
+) -> ArithmeticCircuitStatement<'_, RecommendedTranscript, Ristretto> {
+  let zero_matrix = ScalarMatrix { data: vec![], highest_index: 0 };
+  ArithmeticCircuitStatement::new(
+    generators,
+    zero_matrix.clone(),
+    zero_matrix.clone(),
+    zero_matrix.clone(),
+    vec![],
+    vec![],
+    zero_matrix,
+    ScalarVector(vec![]),
+    PointVector(vec![]),
+    PointVector(vec![]),
+  )
+  .unwrap()
+}
+
+// End-to-end coverage for `prove_norm`/`verify_norm`: previously, every real witness was rejected
+// with `InconsistentWitness` (the guard checked `n`'s self-inner-product instead of the `n`/`l`
+// cross term `WipStatement::prove` actually proves), so this backend had zero test coverage and
+// was entirely non-functional.
+#[test]
+fn test_prove_verify_norm() {
+  let generators = generators::<Ristretto>(1);
+  let reduced = generators.reduce(1).unwrap();
+
+  let a = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+  let b = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+  let witness = ArithmeticCircuitWitness::<Ristretto>::new(
+    ScalarVector(vec![a]),
+    ScalarVector(vec![b]),
+    vec![],
+    vec![],
+  )
+  .unwrap();
+
+  let statement = single_gate_statement(reduced);
+
+  let mut transcript = RecommendedTranscript::new(b"Norm Arithmetic Circuit Test");
+  let proof = statement.clone().prove_norm(&mut OsRng, &mut transcript.clone(), witness).unwrap();
+
+  let mut verifier = generators.batch_verifier();
+  statement.verify_norm(&mut OsRng, &mut verifier, &mut transcript, proof).unwrap();
+  assert!(generators.verify(verifier));
+}
@@ -0,0 +1,65 @@
+use rand_core::OsRng;
+
+use transcript::{Transcript, RecommendedTranscript};
+
+use ciphersuite::{group::ff::Field, Ciphersuite, Ristretto};
+
+use crate::{ScalarVector, fold::{RelaxedWitness, fold}, tests::generators};
+
+fn unrelaxed_witness(
+  aL: Vec<<Ristretto as Ciphersuite>::F>,
+  aR: Vec<<Ristretto as Ciphersuite>::F>,
+) -> RelaxedWitness<Ristretto> {
+  let aL = ScalarVector(aL);
+  let aR = ScalarVector(aR);
+  let aO = ScalarVector((0 .. aL.len()).map(|i| aL[i] * aR[i]).collect());
+  let len = aL.len();
+  RelaxedWitness {
+    aL,
+    aR,
+    aO,
+    c: vec![],
+    v: vec![],
+    E: ScalarVector::new(len),
+    E_mask: <Ristretto as Ciphersuite>::F::ZERO,
+    u: <Ristretto as Ciphersuite>::F::ONE,
+  }
+}
+
+// Folding two valid (u = 1, E = 0) witnesses must produce a folded (instance, witness) pair which
+// still satisfies the relaxed relation `aL ∘ aR = u * aO + E`
+#[test]
+fn test_fold_preserves_relaxed_relation() {
+  let generators = generators::<Ristretto>(2);
+
+  let acc_witness = unrelaxed_witness(
+    vec![<Ristretto as Ciphersuite>::F::from(2u64), <Ristretto as Ciphersuite>::F::from(3u64)],
+    vec![<Ristretto as Ciphersuite>::F::from(5u64), <Ristretto as Ciphersuite>::F::from(7u64)],
+  );
+  let instance_witness = unrelaxed_witness(
+    vec![<Ristretto as Ciphersuite>::F::from(11u64), <Ristretto as Ciphersuite>::F::from(13u64)],
+    vec![<Ristretto as Ciphersuite>::F::from(17u64), <Ristretto as Ciphersuite>::F::from(19u64)],
+  );
+
+  let acc_instance = acc_witness.instance(&generators);
+  let instance_instance = instance_witness.instance(&generators);
+
+  let mut transcript = RecommendedTranscript::new(b"Fold Test");
+  let ((folded_instance, folded_witness), _T) = fold(
+    &mut OsRng,
+    &mut transcript,
+    &generators,
+    (acc_instance, acc_witness),
+    (instance_instance, instance_witness),
+  )
+  .unwrap();
+
+  for i in 0 .. folded_witness.aL.len() {
+    assert_eq!(
+      folded_witness.aL[i] * folded_witness.aR[i],
+      (folded_witness.u * folded_witness.aO[i]) + folded_witness.E[i],
+    );
+  }
+
+  assert_eq!(folded_instance.u, folded_witness.u);
+}
@@ -11,7 +11,7 @@ use ciphersuite::{
 
 use crate::{
   ScalarVector, PointVector,
-  inner_product::{IpStatement, IpWitness},
+  inner_product::{IpStatement, IpWitness, WipStatement, WipWitness},
   tests::generators,
 };
 
@@ -85,3 +85,79 @@ fn test_inner_product() {
   }
   assert!(generators.verify(verifier));
 }
+
+// The weighted inner product relation is
+// P = sum(g_bold * l, h_bold * n, g * (sum_i y^i * n_i * l_i), h_tag * alpha)
+#[test]
+fn test_zero_weighted_inner_product() {
+  let generators = generators::<Ristretto>(1);
+  let reduced = generators.reduce(1).unwrap();
+  let g = reduced.g();
+  let h_tag = <Ristretto as Ciphersuite>::G::random(&mut OsRng);
+  let y = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+  let alpha = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+
+  let P = g * <Ristretto as Ciphersuite>::F::ZERO + h_tag * alpha;
+
+  let statement = WipStatement::<_, Ristretto>::new(reduced, y, h_tag, P);
+  let witness = WipWitness::<Ristretto>::new(
+    ScalarVector::<<Ristretto as Ciphersuite>::F>::new(1),
+    ScalarVector::<<Ristretto as Ciphersuite>::F>::new(1),
+    alpha,
+  )
+  .unwrap();
+
+  let mut transcript = RecommendedTranscript::new(b"Zero WIP Test");
+  let proof = statement.clone().prove(&mut OsRng, &mut transcript.clone(), witness).unwrap();
+
+  let mut verifier = generators.batch_verifier();
+  statement.verify(&mut OsRng, &mut verifier, &mut transcript, proof).unwrap();
+  assert!(generators.verify(verifier));
+}
+
+#[test]
+fn test_weighted_inner_product() {
+  let generators = generators::<Ristretto>(32);
+  let mut verifier = generators.batch_verifier();
+  for i in [1, 2, 4, 8, 16, 32] {
+    let generators = generators.reduce(i).unwrap();
+    let g = generators.g();
+    assert_eq!(generators.len(), i);
+    let mut g_bold = vec![];
+    let mut h_bold = vec![];
+    for i in 0 .. i {
+      g_bold.push(generators.g_bold(i));
+      h_bold.push(generators.h_bold(i));
+    }
+    let g_bold = PointVector::<Ristretto>(g_bold);
+    let h_bold = PointVector::<Ristretto>(h_bold);
+
+    let h_tag = <Ristretto as Ciphersuite>::G::random(&mut OsRng);
+    let y = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+    let alpha = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+
+    let mut l = ScalarVector::<<Ristretto as Ciphersuite>::F>::new(i);
+    let mut n = ScalarVector::<<Ristretto as Ciphersuite>::F>::new(i);
+    for i in 0 .. i {
+      l[i] = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+      n[i] = <Ristretto as Ciphersuite>::F::random(&mut OsRng);
+    }
+
+    let mut weighted = <Ristretto as Ciphersuite>::F::ZERO;
+    let mut y_pow = <Ristretto as Ciphersuite>::F::ONE;
+    for j in 0 .. i {
+      weighted += y_pow * n[j] * l[j];
+      y_pow *= y;
+    }
+
+    let P = g_bold.multiexp(&l) + h_bold.multiexp(&n) + (g * weighted) + (h_tag * alpha);
+
+    let statement = WipStatement::<_, Ristretto>::new(generators, y, h_tag, P);
+    let witness = WipWitness::<Ristretto>::new(n, l, alpha).unwrap();
+
+    let mut transcript = RecommendedTranscript::new(b"WIP Test");
+    let proof = statement.clone().prove(&mut OsRng, &mut transcript.clone(), witness).unwrap();
+    statement.verify(&mut OsRng, &mut verifier, &mut transcript, proof).unwrap();
+  }
+  assert!(generators.verify(verifier));
+}
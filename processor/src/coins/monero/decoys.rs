@@ -0,0 +1,169 @@
+use rand_core::{RngCore, CryptoRng};
+use rand_distr::{Distribution, Gamma};
+
+use monero::VarInt;
+use monero_serai::{rpc::Rpc, transaction::decoys::Decoys};
+
+use crate::CoinError;
+
+// Ring size mandated by the current Monero consensus rules.
+const RING_SIZE: usize = 16;
+// How many times to resample a candidate before giving up, matching the cap
+// `monero_serai::transaction::decoys::sample_global_output_index` uses for the same reason: a
+// pathologically small/early-synced distribution could otherwise make every draw land within the
+// lock window and spin forever.
+const MAX_RESAMPLES: usize = 100;
+// Outputs from the most recent blocks are considered locked and must never be selected as
+// decoys, matching wallet2's default spend-time lock.
+const RECENT_LOCK_WINDOW: usize = 10;
+// wallet2's average block time assumption, used to convert an output's age into a block height.
+const AVERAGE_BLOCK_TIME_SECONDS: f64 = 120.0;
+// The shape/scale of wallet2's gamma distribution over output age, in seconds.
+const GAMMA_SHAPE: f64 = 19.28;
+const GAMMA_SCALE: f64 = 1.0 / 1.61;
+
+/// A locally-maintained mirror of the chain's cumulative RingCT output distribution.
+///
+/// This lets decoy selection run entirely offline, against data already fetched, rather than
+/// asking a remote daemon which outputs we're interested in every time a transaction is built.
+#[derive(Clone, Default)]
+pub struct OutputDistribution {
+  // The cumulative amount of RCT outputs which exist at the end of each block, 0-indexed from
+  // the chain's genesis.
+  cumulative: Vec<u64>,
+}
+
+impl OutputDistribution {
+  pub fn new() -> OutputDistribution {
+    OutputDistribution { cumulative: vec![] }
+  }
+
+  /// The height this distribution has been synced through.
+  pub fn synced_height(&self) -> usize {
+    self.cumulative.len()
+  }
+
+  /// The total amount of RCT outputs synced so far.
+  pub fn total_outputs(&self) -> u64 {
+    self.cumulative.last().copied().unwrap_or(0)
+  }
+
+  /// Extend the locally-held distribution to include every block up to (exclusive of) `height`,
+  /// fetching only the blocks not already known.
+  pub async fn sync(&mut self, rpc: &Rpc, height: usize) -> Result<(), CoinError> {
+    if self.synced_height() >= height {
+      return Ok(());
+    }
+
+    let per_block_counts = rpc
+      .get_output_distribution(self.synced_height(), height)
+      .await
+      .map_err(|_| CoinError::ConnectionError)?;
+    for count in per_block_counts {
+      let prior = self.total_outputs();
+      self.cumulative.push(prior + count);
+    }
+    Ok(())
+  }
+
+  // The global output index one past the last output produced by `height` (exclusive).
+  fn cumulative_through(&self, height: usize) -> u64 {
+    if height == 0 {
+      0
+    } else {
+      self.cumulative.get(height - 1).copied().unwrap_or_else(|| self.total_outputs())
+    }
+  }
+}
+
+/// Selects decoys for a ring signature using Monero's wallet2 gamma-distributed age model,
+/// entirely from a locally-synced [`OutputDistribution`].
+pub struct DecoySelector<'a> {
+  distribution: &'a OutputDistribution,
+  chain_height: usize,
+}
+
+impl<'a> DecoySelector<'a> {
+  pub fn new(distribution: &'a OutputDistribution, chain_height: usize) -> DecoySelector<'a> {
+    DecoySelector { distribution, chain_height }
+  }
+
+  // Sample a candidate global output index from the gamma age distribution, without regard to
+  // whether it's usable.
+  fn sample<R: RngCore + CryptoRng>(&self, rng: &mut R) -> u64 {
+    let total_outputs = self.distribution.total_outputs().max(1);
+    let average_seconds_per_output =
+      ((self.chain_height as f64) * AVERAGE_BLOCK_TIME_SECONDS) / (total_outputs as f64);
+
+    let age_seconds = Gamma::new(GAMMA_SHAPE, GAMMA_SCALE).unwrap().sample(rng).exp();
+    let output_offset_from_tip = (age_seconds / average_seconds_per_output) as u64;
+
+    self.distribution.total_outputs().saturating_sub(1).saturating_sub(output_offset_from_tip)
+  }
+
+  // Sample a usable (unlocked, not already chosen) candidate global output index, bounded by
+  // `MAX_RESAMPLES` tries so a pathologically small/early-synced distribution can't spin forever.
+  fn sample_candidate<R: RngCore + CryptoRng>(
+    &self,
+    rng: &mut R,
+    locked_from: u64,
+    ring: &[u64],
+  ) -> Result<u64, CoinError> {
+    for _ in 0 .. MAX_RESAMPLES {
+      let candidate = self.sample(rng);
+      if (candidate >= locked_from) || ring.contains(&candidate) {
+        continue;
+      }
+      return Ok(candidate);
+    }
+    Err(CoinError::ConnectionError)
+  }
+
+  /// Select `RING_SIZE - 1` decoys for the output at `real_index`, splice it in, and return the
+  /// sorted global output indices alongside the real spend's position within them.
+  ///
+  /// This is entirely synchronous, against the locally-synced [`OutputDistribution`] alone, so
+  /// callers can select every input's ring without holding a lock across the `rpc` calls
+  /// [`Self::fetch`] then needs to turn these indices into a [`Decoys`].
+  pub fn select_indices<R: RngCore + CryptoRng>(
+    &self,
+    rng: &mut R,
+    real_index: u64,
+  ) -> Result<(u8, Vec<u64>), CoinError> {
+    if self.distribution.total_outputs() == 0 {
+      Err(CoinError::ConnectionError)?;
+    }
+
+    // Outputs within the lock window can't be used as decoys
+    let locked_from =
+      self.distribution.cumulative_through(self.chain_height.saturating_sub(RECENT_LOCK_WINDOW));
+
+    let mut ring = vec![real_index];
+    while ring.len() < RING_SIZE {
+      ring.push(self.sample_candidate(rng, locked_from, &ring)?);
+    }
+    ring.sort_unstable();
+
+    let i = u8::try_from(ring.iter().position(|index| *index == real_index).unwrap()).unwrap();
+    Ok((i, ring))
+  }
+
+  /// Fetch the output key/commitment for every member of a [`Self::select_indices`] ring from
+  /// `rpc`, completing it into a [`Decoys`] ready to become a `TxIn`'s `key_offsets` once delta
+  /// encoded.
+  pub async fn fetch(rpc: &Rpc, i: u8, ring: Vec<u64>) -> Result<Decoys, CoinError> {
+    let mut members = Vec::with_capacity(ring.len());
+    for global_index in &ring {
+      members.push(rpc.get_output(*global_index).await.map_err(|_| CoinError::ConnectionError)?);
+    }
+
+    let mut offsets = Vec::with_capacity(ring.len());
+    let mut last = 0;
+    for global_index in ring {
+      offsets.push(VarInt(global_index - last));
+      last = global_index;
+    }
+
+    Ok(Decoys { i, offsets, ring: members })
+  }
+}
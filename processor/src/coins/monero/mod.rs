@@ -11,6 +11,9 @@ use monero_serai::{frost::Ed25519, rpc::Rpc, wallet::{SpendableOutput, SignableT
 
 use crate::{Output as OutputTrait, CoinError, Coin, view_key};
 
+mod decoys;
+use decoys::{OutputDistribution, DecoySelector};
+
 pub struct Output(SpendableOutput);
 impl OutputTrait for Output {
   // If Monero ever does support more than 255 outputs at once, which it could, this u8 could be a
@@ -42,14 +45,16 @@ impl From<SpendableOutput> for Output {
 
 pub struct Monero {
   rpc: Rpc,
-  view: Scalar
+  view: Scalar,
+  output_distribution: std::sync::Mutex<OutputDistribution>
 }
 
 impl Monero {
   pub fn new(url: String) -> Monero {
     Monero {
       rpc: Rpc::new(url),
-      view: dfg::Scalar::from_hash(view_key::<Monero>(0)).0
+      view: dfg::Scalar::from_hash(view_key::<Monero>(0)).0,
+      output_distribution: std::sync::Mutex::new(OutputDistribution::new())
     }
   }
 }
@@ -91,13 +96,43 @@ impl Coin for Monero {
 
   async fn prepare_send<R: RngCore + CryptoRng>(
     &self,
-    _keys: MultisigKeys<Ed25519>,
-    _label: Vec<u8>,
-    _height: usize,
-    _inputs: Vec<Output>,
-    _payments: &[(Address, u64)]
+    rng: &mut R,
+    keys: MultisigKeys<Ed25519>,
+    label: Vec<u8>,
+    height: usize,
+    inputs: Vec<Output>,
+    payments: &[(Address, u64)]
   ) -> Result<SignableTransaction, CoinError> {
-    todo!()
+    // Keep our local mirror of the output distribution synced so decoy selection never has to
+    // ask the daemon which outputs we're actually interested in
+    {
+      let mut output_distribution = self.output_distribution.lock().unwrap();
+      output_distribution.sync(&self.rpc, height).await?;
+    }
+
+    // Select every input's ring indices synchronously, against the locally-synced distribution
+    // alone, so the lock guarding it is never held across the `rpc` calls that follow
+    let ring_indices = {
+      let output_distribution = self.output_distribution.lock().unwrap();
+      let selector = DecoySelector::new(&output_distribution, height);
+      inputs
+        .iter()
+        .map(|input| selector.select_indices(rng, input.0.global_index))
+        .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut rings = Vec::with_capacity(ring_indices.len());
+    for (i, ring) in ring_indices {
+      rings.push(DecoySelector::fetch(&self.rpc, i, ring).await?);
+    }
+
+    SignableTransaction::new(
+      keys,
+      label,
+      inputs.into_iter().map(|output| output.0).collect(),
+      rings,
+      payments.to_vec()
+    ).map_err(|_| CoinError::ConnectionError)
   }
 
   async fn attempt_send<R: RngCore + CryptoRng + std::marker::Send>(